@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ton_token_fuzz::Transfer;
+use ton_token_packer::PackTokens;
+use ton_token_unpacker::fuzz::FuzzTokens;
+use ton_token_unpacker::{ContractResult, UnpackToken};
+
+fuzz_target!(|input: FuzzTokens<Transfer>| {
+    let unpacked: ContractResult<Transfer> = input.into_inner().unpack();
+
+    // Whatever was accepted must survive repacking
+    if let Ok(transfer) = unpacked {
+        let repacked: Transfer = transfer.clone().pack().unpack().unwrap();
+        assert_eq!(repacked, transfer);
+    }
+});
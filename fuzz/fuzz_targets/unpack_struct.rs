@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ton_token_fuzz::Wallet;
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::fuzz::FuzzTokenValue;
+use ton_token_unpacker::{ContractResult, UnpackToken};
+
+fuzz_target!(|input: FuzzTokenValue<Wallet>| {
+    let unpacked: ContractResult<Wallet> = input.into_inner().unpack();
+
+    // Whatever was accepted must survive repacking
+    if let Ok(wallet) = unpacked {
+        let repacked: Wallet = wallet.clone().token_value().unpack().unwrap();
+        assert_eq!(repacked, wallet);
+    }
+});
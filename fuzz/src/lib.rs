@@ -0,0 +1,49 @@
+//! Types exercised by the fuzz targets, mirroring the ones from `ton_token_abi/tests`.
+
+use ton_block::MsgAddressInt;
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::UnpackToken;
+use ton_types::UInt256;
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Clone, Debug, PartialEq)]
+#[abi(repr = "uint16")]
+pub enum Status {
+    Active,
+    Frozen,
+    Closed = 10,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Clone, Debug, PartialEq)]
+pub struct Wallet {
+    #[abi(address)]
+    pub owner: MsgAddressInt,
+    #[abi(uint128)]
+    pub balance: u128,
+    #[abi]
+    pub status: Status,
+    #[abi]
+    pub history: Vec<u32>,
+    #[abi(name = "publicKey")]
+    pub public_key: Vec<u8>,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Clone, Debug, PartialEq)]
+#[abi(plain)]
+pub struct Header {
+    #[abi(uint256)]
+    pub pubkey: UInt256,
+    #[abi(int8)]
+    pub workchain: i8,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Clone, Debug, PartialEq)]
+#[abi(plain)]
+pub struct Transfer {
+    #[abi(flatten)]
+    pub header: Header,
+    #[abi]
+    pub wallet: Wallet,
+    #[abi(bool)]
+    pub bounce: bool,
+}
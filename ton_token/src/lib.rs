@@ -0,0 +1,37 @@
+//! Derives and runtime traits behind a single dependency.
+//!
+//! Types deriving through this crate point the generated code at it:
+//!
+//! ```ignore
+//! use ton_token::{PackAbi, UnpackAbi};
+//!
+//! #[derive(PackAbi, UnpackAbi)]
+//! #[abi(crate = "ton_token")]
+//! struct Data {
+//!     #[abi(uint32)]
+//!     value: u32,
+//! }
+//! ```
+
+pub mod with;
+
+pub use ton_abi;
+pub use ton_block;
+pub use ton_types;
+
+pub use ton_token_packer;
+pub use ton_token_unpacker;
+
+pub use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+
+pub use ton_token_packer::cell::{store_cell, to_boc_base64, to_cell};
+pub use ton_token_unpacker::cell::{from_boc_base64, from_cell};
+
+pub use ton_token_packer::{
+    BuildTokenValue, BuildTokenValues, FriendlyAddress, PackTokens, PublicKey, Signature,
+    StandaloneToken, StoreBuilder, TokenAmount, TokensBuilder,
+};
+pub use ton_token_unpacker::{
+    CellPayload, ContractResult, IntoUnpacker, KnownParamType, KnownParamTypePlain, UnpackToken,
+    UnpackTokenValues, UnpackTokens, UnpackerError,
+};
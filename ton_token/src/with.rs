@@ -0,0 +1,48 @@
+//! Ready-made `#[abi(with = "...")]` adapters, exposing both `pack` and `unpack`.
+//!
+//! ```ignore
+//! #[derive(PackAbi, UnpackAbi)]
+//! #[abi(crate = "ton_token")]
+//! struct Data {
+//!     #[abi(name = "createdAt", with = "ton_token::with::unix_time")]
+//!     created_at: std::time::SystemTime,
+//! }
+//! ```
+
+/// `String` with hex encoded data, stored as `bytes`.
+pub mod hex {
+    pub use ton_token_packer::with::hex::pack;
+    pub use ton_token_unpacker::with::hex::unpack;
+}
+
+/// `SystemTime`, stored as `uint32` unix timestamp in seconds.
+pub mod unix_time {
+    pub use ton_token_packer::with::unix_time::pack;
+    pub use ton_token_unpacker::with::unix_time::unpack;
+}
+
+/// `TokenAmount<9>`, stored as `uint128` nanotons.
+pub mod nanotons {
+    pub use ton_token_packer::with::nanotons::pack;
+    pub use ton_token_unpacker::with::nanotons::unpack;
+}
+
+/// Any amount convertible to and from `u128`, stored as `varuint16`.
+pub mod varuint16 {
+    pub use ton_token_packer::with::varuint16::pack;
+    pub use ton_token_unpacker::with::varuint16::unpack;
+}
+
+/// Any `bitflags` type, stored as unsigned integer. Unknown bits are retained.
+#[cfg(feature = "bitflags")]
+pub mod bitflags {
+    pub use ton_token_packer::with::bitflags::pack;
+    pub use ton_token_unpacker::with::bitflags::unpack;
+}
+
+/// Any `bitflags` type, stored as unsigned integer. Unknown bits are rejected.
+#[cfg(feature = "bitflags")]
+pub mod bitflags_strict {
+    pub use ton_token_packer::with::bitflags::pack;
+    pub use ton_token_unpacker::with::bitflags_strict::unpack;
+}
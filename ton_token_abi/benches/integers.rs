@@ -0,0 +1,98 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ton_abi::TokenValue;
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::store::BuilderData;
+use ton_token_packer::{BuildTokenValue, StoreBuilder};
+use ton_token_unpacker::UnpackToken;
+
+const LEN: u32 = 1000;
+
+#[derive(PackAbi, UnpackAbi, Clone)]
+#[abi(store)]
+struct Item {
+    #[abi(uint8)]
+    kind: u8,
+    #[abi(uint16)]
+    flags: u16,
+    #[abi(uint32)]
+    index: u32,
+    #[abi(uint64)]
+    amount: u64,
+    #[abi(uint128)]
+    total: u128,
+}
+
+fn items() -> Vec<Item> {
+    (0..LEN)
+        .map(|i| Item {
+            kind: i as u8,
+            flags: i as u16,
+            index: i,
+            amount: i as u64 * 1000,
+            total: i as u128 * 1_000_000,
+        })
+        .collect()
+}
+
+// Allocates a token name and a `BigUint` per field, unlike storing.
+fn pack(c: &mut Criterion) {
+    c.bench_function("pack small integers", |b| {
+        b.iter_batched(
+            items,
+            |items| {
+                items
+                    .into_iter()
+                    .map(BuildTokenValue::token_value)
+                    .collect::<Vec<_>>()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn store(c: &mut Criterion) {
+    let items = items();
+    c.bench_function("store small integers", |b| {
+        b.iter(|| {
+            items
+                .iter()
+                .map(|item| {
+                    let mut builder = BuilderData::new();
+                    item.store_into(&mut builder).unwrap();
+                    builder
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+fn unpack(c: &mut Criterion) {
+    let values: Vec<TokenValue> = items().into_iter().map(|item| item.token_value()).collect();
+    c.bench_function("unpack small integers", |b| {
+        b.iter_batched(
+            || values.clone(),
+            |values| {
+                values
+                    .into_iter()
+                    .map(UnpackToken::unpack)
+                    .collect::<Result<Vec<Item>, _>>()
+                    .unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn single(c: &mut Criterion) {
+    c.bench_function("pack u32", |b| b.iter(|| black_box(42u32).token_value()));
+    c.bench_function("unpack u32", |b| {
+        b.iter_batched(
+            || 42u32.token_value(),
+            |value| UnpackToken::<u32>::unpack(value).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, pack, store, unpack, single);
+criterion_main!(benches);
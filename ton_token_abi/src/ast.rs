@@ -1,8 +1,11 @@
+use std::convert::TryFrom;
+
 use either::*;
 use syn::punctuated::Punctuated;
 
 use crate::attr;
 use crate::parsing_context::*;
+use crate::utils::is_abi;
 
 pub struct Container<'a> {
     pub ident: syn::Ident,
@@ -19,6 +22,8 @@ pub enum Data<'a> {
 
 pub struct Variant<'a> {
     pub ident: syn::Ident,
+    pub attrs: attr::Variant,
+    pub discriminant: i128,
     pub style: StructStyle,
     pub fields: Vec<Field<'a>>,
     pub original: &'a syn::Variant,
@@ -47,6 +52,12 @@ impl<'a> Container<'a> {
             }
         };
 
+        match &data {
+            Data::Enum(variants) => check_enum(cx, &attrs, variants),
+            Data::Struct(_, fields) if attrs.store => check_store(cx, fields),
+            Data::Struct(..) => {}
+        }
+
         let item = Self {
             ident: input.ident.clone(),
             attrs,
@@ -59,6 +70,67 @@ impl<'a> Container<'a> {
     }
 }
 
+fn check_store(cx: &ParsingContext, fields: &[Field]) {
+    for field in fields.iter().filter(|f| is_abi(&f.original.attrs)) {
+        let attrs = &field.attrs;
+        if attrs.pack_with.is_some()
+            || attrs.with.is_some()
+            || attrs.flatten
+            || attrs.is_ref
+            || attrs.cell_of.is_some()
+        {
+            cx.error_spanned_by(
+                field.original,
+                "`store` doesn't support `with`, `pack_with`, `flatten`, `ref` or `cell_of` fields",
+            );
+        }
+    }
+}
+
+fn check_enum(cx: &ParsingContext, attrs: &attr::Container, variants: &[Variant]) {
+    let repr = attrs.repr.unwrap_or_default();
+
+    let mut other_variants = variants.iter().filter(|variant| variant.attrs.other);
+    other_variants.next();
+    for variant in other_variants {
+        cx.error_spanned_by(
+            variant.original,
+            "only one variant can be marked as `other`",
+        );
+    }
+
+    for variant in variants {
+        if variant.attrs.other {
+            if !matches!(variant.style, StructStyle::NewType) {
+                cx.error_spanned_by(
+                    variant.original,
+                    "`other` variant must have exactly one unnamed field",
+                );
+            }
+            continue;
+        }
+
+        if !matches!(variant.style, StructStyle::Unit) {
+            cx.error_spanned_by(variant.original, "only unit variants are supported");
+        }
+        if variant.attrs.rename.is_some() && !repr.is_text() {
+            cx.error_spanned_by(
+                variant.original,
+                "`rename` is only supported for string and bytes enums",
+            );
+        }
+        if !repr.contains(variant.discriminant) {
+            cx.error_spanned_by(
+                variant.original,
+                format!(
+                    "discriminant {} doesn't fit into {}",
+                    variant.discriminant, repr
+                ),
+            );
+        }
+    }
+}
+
 impl<'a> Data<'a> {
     #[allow(dead_code)]
     pub fn all_fields(&'a self) -> impl Iterator<Item = &'a Field<'a>> {
@@ -75,27 +147,48 @@ fn enum_from_ast<'a>(
     cx: &ParsingContext,
     variants: &'a Punctuated<syn::Variant, syn::Token![,]>,
 ) -> Option<Vec<Variant<'a>>> {
-    let has_consistent_discriminants = {
-        let mut iter = variants.iter();
-        match iter.next() {
-            Some(variant) => {
-                iter.all(|item| item.discriminant.is_some() == variant.discriminant.is_some())
-            }
-            None => true,
-        }
-    };
-
-    if !has_consistent_discriminants {
-        cx.error_spanned_by(variants, "variant discriminants are not consistent");
-        return None;
-    }
+    let mut next_discriminant = Some(0);
 
     let result: Vec<Variant> = variants
         .iter()
         .flat_map(|variant| {
+            let attrs = attr::Variant::from_ast(cx, variant)?;
             let (style, fields) = struct_from_ast(cx, &variant.fields)?;
+
+            if attrs.other {
+                return Some(Variant {
+                    ident: variant.ident.clone(),
+                    attrs,
+                    discriminant: 0,
+                    style,
+                    fields,
+                    original: variant,
+                });
+            }
+
+            let discriminant = match &variant.discriminant {
+                Some((_, expr)) => match eval_discriminant(expr) {
+                    Ok(value) => value,
+                    Err(message) => {
+                        cx.error_spanned_by(expr, message);
+                        next_discriminant = None;
+                        return None;
+                    }
+                },
+                None => match next_discriminant {
+                    Some(value) => value,
+                    None => {
+                        cx.error_spanned_by(variant, "discriminant overflowed");
+                        return None;
+                    }
+                },
+            };
+            next_discriminant = discriminant.checked_add(1);
+
             Some(Variant {
                 ident: variant.ident.clone(),
+                attrs,
+                discriminant,
                 style,
                 fields,
                 original: variant,
@@ -106,6 +199,69 @@ fn enum_from_ast<'a>(
     (result.len() == variants.len()).then(|| result)
 }
 
+fn eval_discriminant(expr: &syn::Expr) -> Result<i128, &'static str> {
+    const OVERFLOW: &str = "discriminant overflowed";
+
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit
+            .base10_parse::<i128>()
+            .map_err(|_| "invalid discriminant literal"),
+        syn::Expr::Paren(expr) => eval_discriminant(&expr.expr),
+        syn::Expr::Cast(expr) => cast_discriminant(eval_discriminant(&expr.expr)?, &expr.ty),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => eval_discriminant(expr)?.checked_neg().ok_or(OVERFLOW),
+        syn::Expr::Binary(expr) => {
+            let left = eval_discriminant(&expr.left)?;
+            let right = eval_discriminant(&expr.right)?;
+            let shift = || u32::try_from(right).map_err(|_| OVERFLOW);
+            match expr.op {
+                syn::BinOp::Add(_) => left.checked_add(right).ok_or(OVERFLOW),
+                syn::BinOp::Sub(_) => left.checked_sub(right).ok_or(OVERFLOW),
+                syn::BinOp::Mul(_) => left.checked_mul(right).ok_or(OVERFLOW),
+                syn::BinOp::Div(_) => left.checked_div(right).ok_or(OVERFLOW),
+                syn::BinOp::Rem(_) => left.checked_rem(right).ok_or(OVERFLOW),
+                syn::BinOp::Shl(_) => left.checked_shl(shift()?).ok_or(OVERFLOW),
+                syn::BinOp::Shr(_) => left.checked_shr(shift()?).ok_or(OVERFLOW),
+                syn::BinOp::BitAnd(_) => Ok(left & right),
+                syn::BinOp::BitOr(_) => Ok(left | right),
+                syn::BinOp::BitXor(_) => Ok(left ^ right),
+                _ => Err("unsupported discriminant expression"),
+            }
+        }
+        _ => Err("unsupported discriminant expression"),
+    }
+}
+
+/// Applies an `as` cast to an evaluated discriminant, wrapping like rustc does.
+fn cast_discriminant(value: i128, ty: &syn::Type) -> Result<i128, &'static str> {
+    const UNSUPPORTED: &str = "unsupported discriminant cast";
+
+    let ident = match ty {
+        syn::Type::Path(ty) if ty.qself.is_none() => ty.path.get_ident().ok_or(UNSUPPORTED)?,
+        _ => return Err(UNSUPPORTED),
+    };
+    Ok(match ident.to_string().as_str() {
+        "i8" => value as i8 as i128,
+        "u8" => value as u8 as i128,
+        "i16" => value as i16 as i128,
+        "u16" => value as u16 as i128,
+        "i32" => value as i32 as i128,
+        "u32" => value as u32 as i128,
+        "i64" => value as i64 as i128,
+        "u64" => value as u64 as i128,
+        "i128" => value,
+        "u128" if value >= 0 => value,
+        "u128" => return Err("discriminant overflowed"),
+        _ => return Err(UNSUPPORTED),
+    })
+}
+
 fn struct_from_ast<'a>(
     cx: &ParsingContext,
     fields: &'a syn::Fields,
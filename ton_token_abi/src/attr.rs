@@ -8,11 +8,22 @@ use crate::symbol::*;
 
 pub struct Container {
     pub plain: bool,
+    pub repr: Option<EnumRepr>,
+    pub json: bool,
+    pub crate_path: Option<syn::Path>,
+    pub checked: bool,
+    pub store: bool,
 }
 
 impl Container {
     pub fn from_ast(cx: &ParsingContext, input: &syn::DeriveInput) -> Option<Self> {
         let mut plain = BoolAttr::none(cx, PLAIN);
+        let mut repr = Attr::none(cx, REPR);
+        let mut rust_repr = Attr::none(cx, REPR);
+        let mut json = BoolAttr::none(cx, JSON);
+        let mut crate_path = Attr::none(cx, CRATE);
+        let mut checked = BoolAttr::none(cx, CHECKED);
+        let mut store = BoolAttr::none(cx, STORE);
 
         for (from, meta_item) in input
             .attrs
@@ -22,6 +33,22 @@ impl Container {
         {
             match (from, &meta_item) {
                 (AttrFrom::Abi, Meta(Path(word))) if word == PLAIN => plain.set_true(word),
+                (AttrFrom::Abi, Meta(Path(word))) if word == JSON => json.set_true(word),
+                (AttrFrom::Abi, Meta(Path(word))) if word == CHECKED => checked.set_true(word),
+                (AttrFrom::Abi, Meta(Path(word))) if word == STORE => store.set_true(word),
+                (AttrFrom::Abi, Meta(NameValue(m))) if m.path == REPR => {
+                    if let Ok(s) = get_lit_str(cx, REPR, &m.lit) {
+                        match EnumRepr::from(&s.value()) {
+                            Some(value) => repr.set(&m.path, value),
+                            None => cx.error_spanned_by(s, "unknown enum repr"),
+                        }
+                    }
+                }
+                (AttrFrom::Abi, Meta(NameValue(m))) if m.path == CRATE => {
+                    if let Ok(path) = parse_lit_into_path(cx, CRATE, &m.lit) {
+                        crate_path.set(&m.path, path);
+                    }
+                }
                 (AttrFrom::Abi, token) => {
                     cx.error_spanned_by(token, "unexpected token");
                     return None;
@@ -29,7 +56,73 @@ impl Container {
             }
         }
 
-        Some(Self { plain: plain.get() })
+        for attr in input.attrs.iter().filter(|attr| attr.path == REPR) {
+            if let Ok(List(meta)) = attr.parse_meta() {
+                for word in meta.nested.iter().filter_map(|meta| match meta {
+                    Meta(Path(word)) => word.get_ident(),
+                    _ => None,
+                }) {
+                    if let Some(value) = EnumRepr::from_rust(&word.to_string()) {
+                        rust_repr.set(word, value);
+                    }
+                }
+            }
+        }
+
+        let repr = repr.get().or_else(|| rust_repr.get());
+        if repr.is_some() && !matches!(input.data, syn::Data::Enum(_)) {
+            cx.error_spanned_by(input, "repr is only supported for enums");
+        }
+        let store = store.get();
+        if store && !matches!(input.data, syn::Data::Struct(_)) {
+            cx.error_spanned_by(input, "store is only supported for structs");
+        }
+
+        Some(Self {
+            plain: plain.get(),
+            repr,
+            json: json.get(),
+            crate_path: crate_path.get(),
+            checked: checked.get(),
+            store,
+        })
+    }
+}
+
+pub struct Variant {
+    pub rename: Option<String>,
+    pub other: bool,
+}
+
+impl Variant {
+    pub fn from_ast(cx: &ParsingContext, input: &syn::Variant) -> Option<Self> {
+        let mut rename = Attr::none(cx, RENAME);
+        let mut other = BoolAttr::none(cx, OTHER);
+
+        for (from, meta_item) in input
+            .attrs
+            .iter()
+            .flat_map(|attr| get_meta_items(&cx, attr))
+            .flat_map(|item| item.into_iter())
+        {
+            match (from, &meta_item) {
+                (AttrFrom::Abi, Meta(NameValue(m))) if m.path == RENAME => {
+                    if let Ok(s) = get_lit_str(cx, RENAME, &m.lit) {
+                        rename.set(&m.path, s.value());
+                    }
+                }
+                (AttrFrom::Abi, Meta(Path(word))) if word == OTHER => other.set_true(word),
+                (AttrFrom::Abi, token) => {
+                    cx.error_spanned_by(token, "unexpected token");
+                    return None;
+                }
+            }
+        }
+
+        Some(Self {
+            rename: rename.get(),
+            other: other.get(),
+        })
     }
 }
 
@@ -38,6 +131,10 @@ pub struct Field {
     pub type_name: Option<TypeName>,
     pub pack_with: Option<syn::Expr>,
     pub unpack_with: Option<syn::Expr>,
+    pub with: Option<syn::Path>,
+    pub flatten: bool,
+    pub is_ref: bool,
+    pub cell_of: Option<syn::Type>,
 }
 
 impl Field {
@@ -46,6 +143,10 @@ impl Field {
         let mut type_name = Attr::none(cx, TYPE_NAME);
         let mut pack_with = Attr::none(cx, PACK_WITH);
         let mut unpack_with = Attr::none(cx, UNPACK_WITH);
+        let mut with = Attr::none(cx, WITH);
+        let mut flatten = BoolAttr::none(cx, FLATTEN);
+        let mut is_ref = BoolAttr::none(cx, REF);
+        let mut cell_of = Attr::none(cx, CELL_OF);
 
         for (from, meta_item) in input
             .attrs
@@ -59,6 +160,8 @@ impl Field {
                         name.set(&m.path, s.value());
                     }
                 }
+                (AttrFrom::Abi, Meta(Path(word))) if word == FLATTEN => flatten.set_true(word),
+                (AttrFrom::Abi, Meta(Path(word))) if word == REF => is_ref.set_true(word),
                 (AttrFrom::Abi, Meta(Path(word))) => {
                     if let Some(word) = word.get_ident() {
                         let pt = TypeName::from(&word.to_string());
@@ -79,6 +182,16 @@ impl Field {
                         unpack_with.set(&m.path, expr);
                     }
                 }
+                (AttrFrom::Abi, Meta(NameValue(m))) if m.path == WITH => {
+                    if let Ok(path) = parse_lit_into_path(cx, WITH, &m.lit) {
+                        with.set(&m.path, path);
+                    }
+                }
+                (AttrFrom::Abi, Meta(NameValue(m))) if m.path == CELL_OF => {
+                    if let Ok(ty) = parse_lit_into_type(cx, CELL_OF, &m.lit) {
+                        cell_of.set(&m.path, ty);
+                    }
+                }
                 (AttrFrom::Abi, token) => {
                     cx.error_spanned_by(token, "unexpected token");
                     return None;
@@ -86,11 +199,62 @@ impl Field {
             }
         }
 
+        if flatten.get()
+            && (name.get_ref().is_some()
+                || type_name.get_ref().is_some()
+                || pack_with.get_ref().is_some()
+                || unpack_with.get_ref().is_some()
+                || with.get_ref().is_some()
+                || is_ref.get()
+                || cell_of.get_ref().is_some())
+        {
+            cx.error_spanned_by(
+                input,
+                "`flatten` cannot be combined with other abi attributes",
+            );
+        }
+
+        if is_ref.get() && (pack_with.get_ref().is_some() || unpack_with.get_ref().is_some()) {
+            cx.error_spanned_by(
+                input,
+                "`ref` cannot be combined with `pack_with` or `unpack_with`",
+            );
+        }
+
+        let cell_of = cell_of.get_with_tokens();
+        if let Some((tokens, _)) = &cell_of {
+            if type_name.get_ref().is_some()
+                || pack_with.get_ref().is_some()
+                || unpack_with.get_ref().is_some()
+                || with.get_ref().is_some()
+                || is_ref.get()
+            {
+                cx.error_spanned_by(tokens, "`cell_of` can only be combined with `name`");
+            }
+        }
+
+        let with = with.get_with_tokens();
+        if let Some((tokens, _)) = &with {
+            if pack_with.get_ref().is_some() || unpack_with.get_ref().is_some() {
+                cx.error_spanned_by(
+                    tokens,
+                    "`with` cannot be combined with `pack_with` or `unpack_with`",
+                );
+            }
+            if type_name.get_ref().is_some() {
+                cx.error_spanned_by(tokens, "`with` cannot be combined with a type name");
+            }
+        }
+
         Some(Self {
             name: name.get(),
             type_name: type_name.get(),
             pack_with: pack_with.get(),
             unpack_with: unpack_with.get(),
+            with: with.map(|(_, path)| path),
+            flatten: flatten.get(),
+            is_ref: is_ref.get(),
+            cell_of: cell_of.map(|(_, ty)| ty),
         })
     }
 }
@@ -106,6 +270,28 @@ fn parse_lit_into_expr(
     })
 }
 
+fn parse_lit_into_path(
+    cx: &ParsingContext,
+    attr_name: Symbol,
+    lit: &syn::Lit,
+) -> Result<syn::Path, ()> {
+    let string = get_lit_str(cx, attr_name, lit)?;
+    parse_lit_str(string).map_err(|_| {
+        cx.error_spanned_by(lit, format!("failed to parse path: {:?}", string.value()))
+    })
+}
+
+fn parse_lit_into_type(
+    cx: &ParsingContext,
+    attr_name: Symbol,
+    lit: &syn::Lit,
+) -> Result<syn::Type, ()> {
+    let string = get_lit_str(cx, attr_name, lit)?;
+    parse_lit_str(string).map_err(|_| {
+        cx.error_spanned_by(lit, format!("failed to parse type: {:?}", string.value()))
+    })
+}
+
 fn parse_lit_str<T>(s: &syn::LitStr) -> syn::parse::Result<T>
 where
     T: syn::parse::Parse,
@@ -259,7 +445,10 @@ impl<'c, T> Attr<'c, T> {
         self.value
     }
 
-    #[allow(dead_code)]
+    fn get_ref(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
     fn get_with_tokens(self) -> Option<(TokenStream, T)> {
         match self.value {
             Some(value) => Some((self.tokens, value)),
@@ -332,3 +521,81 @@ impl TypeName {
         }
     }
 }
+
+#[derive(Copy, Clone)]
+pub enum EnumRepr {
+    Uint(usize),
+    Int(usize),
+    String,
+    Bytes,
+}
+
+impl EnumRepr {
+    fn from(input: &str) -> Option<EnumRepr> {
+        if input == "string" {
+            Some(EnumRepr::String)
+        } else if input == "bytes" {
+            Some(EnumRepr::Bytes)
+        } else if let Some(size) = input.strip_prefix("uint") {
+            Self::with_size(false, size)
+        } else if let Some(size) = input.strip_prefix("int") {
+            Self::with_size(true, size)
+        } else {
+            None
+        }
+    }
+
+    fn from_rust(input: &str) -> Option<EnumRepr> {
+        if let Some(size) = input.strip_prefix('u') {
+            Self::with_size(false, size)
+        } else if let Some(size) = input.strip_prefix('i') {
+            Self::with_size(true, size)
+        } else {
+            None
+        }
+    }
+
+    fn with_size(signed: bool, size: &str) -> Option<EnumRepr> {
+        match size.parse() {
+            Ok(size @ 8) | Ok(size @ 16) | Ok(size @ 32) | Ok(size @ 64) if signed => {
+                Some(EnumRepr::Int(size))
+            }
+            Ok(size @ 8) | Ok(size @ 16) | Ok(size @ 32) | Ok(size @ 64) => {
+                Some(EnumRepr::Uint(size))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        matches!(self, EnumRepr::String | EnumRepr::Bytes)
+    }
+
+    pub fn contains(&self, value: i128) -> bool {
+        match *self {
+            EnumRepr::Uint(size) => (0..1i128 << size).contains(&value),
+            EnumRepr::Int(size) => {
+                let max = (1i128 << (size - 1)) - 1;
+                (-max - 1..=max).contains(&value)
+            }
+            EnumRepr::String | EnumRepr::Bytes => true,
+        }
+    }
+}
+
+impl Default for EnumRepr {
+    fn default() -> Self {
+        EnumRepr::Uint(8)
+    }
+}
+
+impl std::fmt::Display for EnumRepr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnumRepr::Uint(size) => write!(f, "uint{}", size),
+            EnumRepr::Int(size) => write!(f, "int{}", size),
+            EnumRepr::String => f.write_str("string"),
+            EnumRepr::Bytes => f.write_str("bytes"),
+        }
+    }
+}
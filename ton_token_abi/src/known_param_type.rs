@@ -0,0 +1,140 @@
+use quote::quote;
+
+use crate::ast::*;
+use crate::attr::{EnumRepr, TypeName};
+use crate::parsing_context::*;
+use crate::utils::*;
+
+pub fn impl_derive_known_param_type(
+    input: syn::DeriveInput,
+) -> Result<proc_macro2::TokenStream, Vec<syn::Error>> {
+    let cx = ParsingContext::new();
+    let container = match Container::from_ast(&cx, &input) {
+        Some(container) => container,
+        None => return Err(cx.check().unwrap_err()),
+    };
+
+    if let Data::Struct(_, fields) = &container.data {
+        for field in fields {
+            if field.attrs.pack_with.is_some()
+                || field.attrs.unpack_with.is_some()
+                || field.attrs.with.is_some()
+            {
+                cx.error_spanned_by(
+                    field.original,
+                    "param type of fields with custom packers can't be derived",
+                );
+            }
+        }
+    }
+    cx.check()?;
+
+    let ident = &container.ident;
+    let result = match &container.data {
+        Data::Enum(_) => {
+            let body = match container.attrs.repr.unwrap_or_default() {
+                EnumRepr::Uint(size) => quote!(ton_abi::ParamType::Uint(#size)),
+                EnumRepr::Int(size) => quote!(ton_abi::ParamType::Int(#size)),
+                EnumRepr::String => quote!(ton_abi::ParamType::String),
+                EnumRepr::Bytes => quote!(ton_abi::ParamType::Bytes),
+            };
+            quote! {
+                impl ton_token_unpacker::KnownParamType for #ident {
+                    fn param_type() -> ton_abi::ParamType {
+                        #body
+                    }
+                }
+            }
+        }
+        Data::Struct(_, fields) => {
+            let body = serialize_struct(fields);
+            if container.attrs.plain {
+                quote! {
+                    impl ton_token_unpacker::KnownParamTypePlain for #ident {
+                        fn param_type() -> Vec<ton_abi::Param> {
+                            #body
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    impl ton_token_unpacker::KnownParamType for #ident {
+                        fn param_type() -> ton_abi::ParamType {
+                            ton_abi::ParamType::Tuple({ #body })
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    Ok(with_crate_path(
+        container.attrs.crate_path.as_ref(),
+        &["ton_abi", "ton_token_unpacker"],
+        result,
+    ))
+}
+
+fn serialize_struct(fields: &[Field]) -> proc_macro2::TokenStream {
+    let build_fields = fields.iter().map(|f| {
+        let ty = f.ty;
+
+        if f.attrs.flatten {
+            quote! {
+                params.extend(<#ty as ton_token_unpacker::KnownParamTypePlain>::param_type())
+            }
+        } else if is_abi(&f.original.attrs) {
+            let name = f.original.ident.as_ref().unwrap();
+            let field_name = match &f.attrs.name {
+                Some(v) => v.clone(),
+                None => name.to_string(),
+            };
+
+            let kind = match (&f.attrs.cell_of, &f.attrs.type_name) {
+                (Some(_), _) => quote!(ton_abi::ParamType::Cell),
+                (None, Some(type_name)) => get_handler(type_name),
+                (None, None) => quote! {
+                    <#ty as ton_token_unpacker::KnownParamType>::param_type()
+                },
+            };
+            let kind = if f.attrs.is_ref {
+                quote!(ton_abi::ParamType::Ref(Box::new(#kind)))
+            } else {
+                kind
+            };
+
+            quote! {
+                params.push(ton_abi::Param {
+                    name: #field_name.to_string(),
+                    kind: #kind,
+                })
+            }
+        } else {
+            quote! {} // do nothing
+        }
+    });
+
+    quote! {
+        let mut params: Vec<ton_abi::Param> = Vec::new();
+        #(#build_fields;)*
+        params
+    }
+}
+
+fn get_handler(type_name: &TypeName) -> proc_macro2::TokenStream {
+    match type_name {
+        TypeName::Int8 => quote!(ton_abi::ParamType::Int(8)),
+        TypeName::Uint8 => quote!(ton_abi::ParamType::Uint(8)),
+        TypeName::Uint16 => quote!(ton_abi::ParamType::Uint(16)),
+        TypeName::Uint32 => quote!(ton_abi::ParamType::Uint(32)),
+        TypeName::Uint64 => quote!(ton_abi::ParamType::Uint(64)),
+        TypeName::Uint128 => quote!(ton_abi::ParamType::Uint(128)),
+        TypeName::Uint160 => quote!(ton_abi::ParamType::Uint(160)),
+        TypeName::Uint256 => quote!(ton_abi::ParamType::Uint(256)),
+        TypeName::Bool => quote!(ton_abi::ParamType::Bool),
+        TypeName::Cell => quote!(ton_abi::ParamType::Cell),
+        TypeName::Address => quote!(ton_abi::ParamType::Address),
+        TypeName::Biguint128 => quote!(ton_abi::ParamType::Uint(128)),
+        TypeName::None => unreachable!(),
+    }
+}
@@ -1,11 +1,13 @@
 mod ast;
 mod attr;
+mod known_param_type;
 mod pack_abi;
 mod parsing_context;
 mod symbol;
 mod unpack_abi;
 mod utils;
 
+use self::known_param_type::*;
 use self::pack_abi::*;
 use self::unpack_abi::*;
 use quote::quote;
@@ -27,6 +29,14 @@ pub fn derive_unpack_abi(input: proc_macro::TokenStream) -> proc_macro::TokenStr
         .into()
 }
 
+#[proc_macro_derive(KnownParamType, attributes(abi))]
+pub fn derive_known_param_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    impl_derive_known_param_type(input)
+        .unwrap_or_else(to_compile_errors)
+        .into()
+}
+
 fn to_compile_errors(errors: Vec<syn::Error>) -> proc_macro2::TokenStream {
     let compile_errors = errors.iter().map(syn::Error::to_compile_error);
     quote!(#(#compile_errors)*)
@@ -1,8 +1,9 @@
-use proc_macro2::Ident;
-use quote::quote;
+use proc_macro2::Literal;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 
 use crate::ast::*;
-use crate::attr::TypeName;
+use crate::attr::{EnumRepr, TypeName};
 use crate::parsing_context::*;
 use crate::utils::*;
 
@@ -17,8 +18,8 @@ pub fn impl_derive_pack_abi(
     cx.check()?;
 
     let ident = &container.ident;
-    let result = match &container.data {
-        Data::Enum(variants) => {
+    let mut result = match (&container.data, container.attrs.checked) {
+        (Data::Enum(variants), false) => {
             let body = serialize_enum(&container, variants);
             quote! {
                 impl ton_token_packer::BuildTokenValue for #ident {
@@ -30,7 +31,21 @@ pub fn impl_derive_pack_abi(
                 impl ton_token_packer::StandaloneToken for #ident {}
             }
         }
-        Data::Struct(_, fields) => {
+        (Data::Enum(variants), true) => {
+            let body = serialize_enum(&container, variants);
+            quote! {
+                impl ton_token_packer::TryBuildTokenValue for #ident {
+                    fn try_token_value(
+                        self,
+                    ) -> std::result::Result<ton_abi::TokenValue, ton_token_packer::PackerError> {
+                        #body
+                    }
+                }
+
+                impl ton_token_packer::StandaloneToken for #ident {}
+            }
+        }
+        (Data::Struct(_, fields), false) => {
             if container.attrs.plain {
                 let body = serialize_struct(&container, fields, StructType::Plain);
                 quote! {
@@ -39,6 +54,15 @@ pub fn impl_derive_pack_abi(
                             #body
                         }
                     }
+
+                    impl ton_token_packer::BuildTokenValues for #ident {
+                        fn token_values(self) -> Vec<ton_abi::TokenValue> {
+                            ton_token_packer::PackTokens::pack(self)
+                                .into_iter()
+                                .map(|token| token.value)
+                                .collect()
+                        }
+                    }
                 }
             } else {
                 let body = serialize_struct(&container, fields, StructType::Tuple);
@@ -48,11 +72,94 @@ pub fn impl_derive_pack_abi(
                             #body
                         }
                     }
+
+                    impl ton_token_packer::StandaloneToken for #ident {}
+                }
+            }
+        }
+        (Data::Struct(_, fields), true) => {
+            if container.attrs.plain {
+                let body = serialize_struct(&container, fields, StructType::Plain);
+                quote! {
+                    impl ton_token_packer::TryPackTokens for #ident {
+                        fn try_pack(
+                            self,
+                        ) -> std::result::Result<Vec<ton_abi::Token>, ton_token_packer::PackerError> {
+                            #body
+                        }
+                    }
+                }
+            } else {
+                let body = serialize_struct(&container, fields, StructType::Tuple);
+                quote! {
+                    impl ton_token_packer::TryBuildTokenValue for #ident {
+                        fn try_token_value(
+                            self,
+                        ) -> std::result::Result<ton_abi::TokenValue, ton_token_packer::PackerError> {
+                            #body
+                        }
+                    }
+
+                    impl ton_token_packer::StandaloneToken for #ident {}
                 }
             }
         }
     };
-    Ok(result)
+
+    if let (Data::Struct(_, fields), true) = (&container.data, container.attrs.store) {
+        let store = store_struct(&container, fields);
+        result = quote! {
+            #result
+
+            #store
+        };
+    }
+
+    if container.attrs.json {
+        let serialize = match (container.attrs.plain, container.attrs.checked) {
+            (true, false) => quote! {
+                let tokens = ton_token_packer::PackTokens::pack(std::clone::Clone::clone(self));
+                ton_token_packer::json::serialize_tokens(&tokens, serializer)
+            },
+            (false, false) => quote! {
+                let value = ton_token_packer::BuildTokenValue::token_value(std::clone::Clone::clone(self));
+                ton_token_packer::json::serialize_token_value(&value, serializer)
+            },
+            (true, true) => quote! {
+                let tokens = ton_token_packer::TryPackTokens::try_pack(std::clone::Clone::clone(self))
+                    .map_err(<S::Error as ton_token_packer::serde::ser::Error>::custom)?;
+                ton_token_packer::json::serialize_tokens(&tokens, serializer)
+            },
+            (false, true) => quote! {
+                let value = ton_token_packer::TryBuildTokenValue::try_token_value(std::clone::Clone::clone(self))
+                    .map_err(<S::Error as ton_token_packer::serde::ser::Error>::custom)?;
+                ton_token_packer::json::serialize_token_value(&value, serializer)
+            },
+        };
+        // `Serialize` packs a clone of the value, the bound reports a missing `Clone` at the type
+        let clone_bound = quote_spanned!(ident.span()=> #ident: std::clone::Clone);
+        result = quote! {
+            #result
+
+            impl ton_token_packer::serde::Serialize for #ident
+            where
+                #clone_bound,
+            {
+                fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+                where
+                    S: ton_token_packer::serde::Serializer,
+                {
+                    #serialize
+                }
+            }
+        };
+    }
+
+    Ok(with_crate_path(
+        container.attrs.crate_path.as_ref(),
+        &["ton_abi", "ton_token_packer"],
+        result,
+    ))
 }
 
 enum StructType {
@@ -62,39 +169,88 @@ enum StructType {
 
 fn serialize_enum(container: &Container, variants: &[Variant]) -> proc_macro2::TokenStream {
     let name = &container.ident;
+    let repr = container.attrs.repr.unwrap_or_default();
 
-    let build_variants = variants
+    // Payloads of the `other` variant are checked against tags of the declared ones
+    let tags = variants
         .iter()
-        .filter_map(|variant| {
-            variant
-                .original
-                .discriminant
-                .as_ref()
-                .map(|(_, discriminant)| (variant.ident.clone(), discriminant))
-        })
-        .map(|(ident, discriminant)| {
-            let token = quote::ToTokens::to_token_stream(discriminant).to_string();
-            let number = token.parse::<u8>().unwrap();
+        .filter(|variant| !variant.attrs.other)
+        .map(|variant| build_enum_tag(repr, variant))
+        .collect::<Vec<_>>();
 
-            quote! {
-                #name::#ident => #number.token_value()
-            }
-        });
+    let build_variants = variants.iter().map(|variant| {
+        let ident = &variant.ident;
 
-    quote! {
+        if variant.attrs.other {
+            let value = build_enum_value(repr, quote!(value));
+            return if container.attrs.checked {
+                quote! {
+                    #name::#ident(value) => ton_token_packer::checked::try_other_variant(#value, &[#(#tags),*])?
+                }
+            } else {
+                quote! {
+                    #name::#ident(value) => ton_token_packer::checked::other_variant(#value, &[#(#tags),*])
+                }
+            };
+        }
+
+        let value = build_enum_tag(repr, variant);
+
+        quote! {
+            #name::#ident => #value
+        }
+    });
+
+    let body = quote! {
         match self {
             #(#build_variants,)*
         }
+    };
+
+    if container.attrs.checked {
+        quote!(Ok(#body))
+    } else {
+        body
+    }
+}
+
+fn build_enum_tag(repr: EnumRepr, variant: &Variant) -> proc_macro2::TokenStream {
+    let tag = match repr {
+        EnumRepr::Uint(_) => Literal::u64_suffixed(variant.discriminant as u64),
+        EnumRepr::Int(_) => Literal::i64_suffixed(variant.discriminant as i64),
+        EnumRepr::String | EnumRepr::Bytes => Literal::string(&variant_tag(variant)),
+    };
+    build_enum_value(repr, quote!(#tag))
+}
+
+fn build_enum_value(repr: EnumRepr, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match repr {
+        EnumRepr::Uint(size) => quote! {
+            ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from(#value), size: #size })
+        },
+        EnumRepr::Int(size) => quote! {
+            ton_abi::TokenValue::Int(ton_abi::Int { number: ton_token_packer::num_bigint::BigInt::from(#value), size: #size })
+        },
+        EnumRepr::String => quote! {
+            ton_abi::TokenValue::String(std::string::String::from(#value))
+        },
+        EnumRepr::Bytes => quote! {
+            ton_abi::TokenValue::Bytes(std::string::String::from(#value).into_bytes())
+        },
     }
 }
 
 fn serialize_struct(
-    _container: &Container,
+    container: &Container,
     fields: &[Field],
     struct_type: StructType,
 ) -> proc_macro2::TokenStream {
+    let checked = container.attrs.checked;
+
+    // Flattened fields may add more tokens, this only avoids regrowth for the common case
+    let capacity = fields.iter().filter(|f| is_abi(&f.original.attrs)).count();
     let definition = quote! {
-        let mut tokens: Vec<ton_abi::Token> = Vec::new();
+        let mut tokens: Vec<ton_abi::Token> = Vec::with_capacity(#capacity);
     };
 
     let build_fields = fields.iter().map(|f| {
@@ -105,114 +261,154 @@ fn serialize_struct(
                 None => name.to_string(),
             };
 
-            match &f.attrs.pack_with {
-                Some(data) => {
+            if f.attrs.flatten {
+                return if checked {
                     quote! {
-                        tokens.push(#data(#field_name, self.#name))
+                        tokens.extend(ton_token_packer::TryPackTokens::try_pack(self.#name)?)
                     }
-                }
-                None => match &f.attrs.type_name {
-                    Some(type_name) => {
-                        let handler = get_handler(type_name, name);
+                } else {
+                    quote! {
+                        tokens.extend(ton_token_packer::PackTokens::pack(self.#name))
+                    }
+                };
+            }
+
+            if let Some(cell_of) = &f.attrs.cell_of {
+                let value = if checked {
+                    quote! {
+                        ton_token_packer::cell::try_cell_of::<#cell_of>(self.#name)
+                            .map_err(|e| ton_token_packer::PackerError::in_field(e, #field_name))?
+                    }
+                } else {
+                    quote! {
+                        ton_token_packer::cell::cell_of::<#cell_of>(self.#name)
+                    }
+                };
+                return quote! {
+                    tokens.push(ton_abi::Token::new(#field_name, #value))
+                };
+            }
+
+            let value = match (&f.attrs.pack_with, &f.attrs.with) {
+                (Some(data), _) => {
+                    return if checked {
                         quote! {
-                            tokens.push(ton_abi::Token::new(#field_name, #handler))
+                            tokens.push(ton_token_packer::checked::check_token(#data(#field_name, self.#name))?)
                         }
-                    }
-                    None => {
+                    } else {
                         quote! {
-                            tokens.push(ton_abi::Token::new(#field_name, self.#name.token_value()))
+                            tokens.push(#data(#field_name, self.#name))
                         }
+                    };
+                }
+                (None, Some(with)) if checked => quote! {
+                    #with::pack(self.#name).and_then(ton_token_packer::checked::checked)
+                },
+                (None, Some(with)) => quote! {
+                    ton_token_packer::checked::expect_field(#with::pack(self.#name), #field_name)
+                },
+                (None, None) => match &f.attrs.type_name {
+                    Some(type_name) => {
+                        let value = if is_smart_pointer(f.ty) {
+                            quote!(ton_token_packer::abi_type::IntoInner::into_inner(self.#name))
+                        } else {
+                            quote!(self.#name)
+                        };
+                        get_handler(type_name, f.ty, value, checked)
                     }
+                    None if checked => quote! {
+                        ton_token_packer::TryBuildTokenValue::try_token_value(self.#name)
+                    },
+                    None => quote! {
+                        self.#name.token_value()
+                    },
                 },
+            };
+
+            // In the checked mode every value is a `Result` which is checked before wrapping
+            let value = if checked {
+                quote! {
+                    #value.map_err(|e| ton_token_packer::PackerError::in_field(e, #field_name))?
+                }
+            } else {
+                value
+            };
+
+            let value = if f.attrs.is_ref {
+                quote! {
+                    ton_abi::TokenValue::Ref(Box::new(#value))
+                }
+            } else {
+                value
+            };
+
+            quote! {
+                tokens.push(ton_abi::Token::new(#field_name, #value))
             }
         } else {
             quote! {} // do nothing
         }
     });
 
-    match struct_type {
-        StructType::Plain => {
-            quote! {
-                #definition
-                #(#build_fields;)*
-                return tokens;
-            }
-        }
-        StructType::Tuple => {
-            quote! {
-                #definition
-                #(#build_fields;)*
-                return ton_abi::TokenValue::Tuple(tokens);
-            }
-        }
+    let result = match struct_type {
+        StructType::Plain => quote!(tokens),
+        StructType::Tuple => quote!(ton_abi::TokenValue::Tuple(tokens)),
+    };
+    let result = if checked { quote!(Ok(#result)) } else { result };
+
+    quote! {
+        #definition
+        #(#build_fields;)*
+        return #result;
     }
 }
 
-fn get_handler(type_name: &TypeName, name: &Ident) -> proc_macro2::TokenStream {
-    match type_name {
-        TypeName::Int8 => {
-            quote! {
-                ton_abi::TokenValue::Int(ton_abi::Int { number: ton_token_packer::num_bigint::BigInt::from(self.#name), size: 8 })
-            }
-        }
-        TypeName::Uint8 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from(self.#name), size: 8 })
-            }
-        }
-        TypeName::Uint16 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from(self.#name), size: 16 })
-            }
-        }
-        TypeName::Uint32 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from(self.#name), size: 32 })
-            }
-        }
-        TypeName::Uint64 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from(self.#name), size: 64 })
-            }
-        }
-        TypeName::Uint128 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from(self.#name), size: 128 })
-            }
-        }
-        TypeName::Uint160 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from_bytes_be(self.#name.as_slice()), size: 160 })
-            }
-        }
-        TypeName::Uint256 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from_bytes_be(self.#name.as_slice()), size: 256 })
-            }
-        }
-        TypeName::Address => {
+/// Implements `StoreBuilder` for an `#[abi(store)]` struct, every field type must implement it.
+fn store_struct(container: &Container, fields: &[Field]) -> proc_macro2::TokenStream {
+    let ident = &container.ident;
+    let store_fields = fields
+        .iter()
+        .filter(|f| is_abi(&f.original.attrs))
+        .map(|f| {
+            let name = f.original.ident.as_ref().unwrap();
+            let store_into = quote_spanned! {f.ty.span()=>
+                <_ as ton_token_packer::store::StoreBuilder>::store_into
+            };
             quote! {
-                ton_abi::TokenValue::Address(match self.#name {
-                    ton_block::MsgAddressInt::AddrStd(addr) => ton_block::MsgAddress::AddrStd(addr),
-                    ton_block::MsgAddressInt::AddrVar(addr) => ton_block::MsgAddress::AddrVar(addr),
-                })
+                #store_into(&self.#name, builder)?
             }
-        }
-        TypeName::Cell => {
-            quote! {
-                ton_abi::TokenValue::Cell(self.#name)
+        });
+
+    quote! {
+        impl ton_token_packer::store::StoreBuilder for #ident {
+            fn store_into(
+                &self,
+                builder: &mut ton_token_packer::store::BuilderData,
+            ) -> std::result::Result<(), ton_token_packer::PackerError> {
+                #(#store_fields;)*
+                Ok(())
             }
         }
-        TypeName::Bool => {
-            quote! {
-                ton_abi::TokenValue::Bool(self.#name)
-            }
+    }
+}
+
+fn get_handler(
+    type_name: &TypeName,
+    ty: &syn::Type,
+    value: proc_macro2::TokenStream,
+    checked: bool,
+) -> proc_macro2::TokenStream {
+    let marker = type_name_marker(quote!(ton_token_packer), type_name);
+    let into_abi = if checked {
+        quote_spanned! {ty.span()=>
+            <_ as ton_token_packer::abi_type::TryIntoAbi<#marker>>::try_into_abi
         }
-        TypeName::Biguint128 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: ton_token_packer::num_bigint::BigUint::from_bytes_be(self.#name.as_slice()), size: 128 })
-            }
+    } else {
+        quote_spanned! {ty.span()=>
+            <_ as ton_token_packer::abi_type::IntoAbi<#marker>>::into_abi
         }
-        TypeName::None => unreachable!(),
+    };
+    quote! {
+        #into_abi(#value)
     }
 }
@@ -13,12 +13,25 @@ define_symbols! {
 
     // container attributes
     PLAIN => "plain",
+    REPR => "repr",
+    JSON => "json",
+    CRATE => "crate",
+    CHECKED => "checked",
+    STORE => "store",
+
+    // variant attributes
+    RENAME => "rename",
+    OTHER => "other",
 
     // field attributes
     NAME => "name",
     TYPE_NAME => "type",
     PACK_WITH => "pack_with",
     UNPACK_WITH => "unpack_with",
+    WITH => "with",
+    FLATTEN => "flatten",
+    REF => "ref",
+    CELL_OF => "cell_of",
 }
 
 #[derive(Copy, Clone)]
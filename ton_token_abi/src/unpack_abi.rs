@@ -1,7 +1,9 @@
-use quote::quote;
+use proc_macro2::Literal;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 
 use crate::ast::*;
-use crate::attr::TypeName;
+use crate::attr::{EnumRepr, TypeName};
 use crate::parsing_context::*;
 use crate::utils::*;
 
@@ -16,7 +18,7 @@ pub fn impl_derive_unpack_abi(
     cx.check()?;
 
     let ident = &container.ident;
-    let result = match &container.data {
+    let mut result = match &container.data {
         Data::Enum(variants) => {
             let body = serialize_enum(&container, variants);
             quote! {
@@ -25,17 +27,40 @@ pub fn impl_derive_unpack_abi(
                         #body
                     }
                 }
-
-                impl ton_token_unpacker::StandaloneToken for #ident {}
             }
         }
         Data::Struct(_, fields) => {
             if container.attrs.plain {
                 let body = serialize_struct(&container, fields, StructType::Plain);
+                let values_body = serialize_struct(&container, fields, StructType::Values);
                 quote! {
+                    impl ton_token_unpacker::UnpackTokens for #ident {
+                        fn unpack_tokens<I>(tokens: &mut I) -> ton_token_unpacker::ContractResult<Self>
+                        where
+                            I: Iterator<Item = ton_abi::Token>,
+                        {
+                            #body
+                        }
+
+                        fn unpack_token_values<I>(tokens: &mut I) -> ton_token_unpacker::ContractResult<Self>
+                        where
+                            I: Iterator<Item = ton_abi::TokenValue>,
+                        {
+                            #values_body
+                        }
+                    }
+
                     impl ton_token_unpacker::UnpackToken<#ident> for Vec<ton_abi::Token> {
                         fn unpack(self) -> ton_token_unpacker::ContractResult<#ident> {
-                            #body
+                            let mut tokens = self.into_iter();
+                            ton_token_unpacker::UnpackTokens::unpack_tokens(&mut tokens)
+                        }
+                    }
+
+                    impl ton_token_unpacker::UnpackTokenValues<#ident> for Vec<ton_abi::TokenValue> {
+                        fn unpack_values(self) -> ton_token_unpacker::ContractResult<#ident> {
+                            let mut tokens = self.into_iter();
+                            ton_token_unpacker::UnpackTokens::unpack_token_values(&mut tokens)
                         }
                     }
                 }
@@ -51,41 +76,117 @@ pub fn impl_derive_unpack_abi(
             }
         }
     };
-    Ok(result)
+
+    if container.attrs.json {
+        let deserialize = if container.attrs.plain {
+            quote! {
+                let params = <#ident as ton_token_unpacker::KnownParamTypePlain>::param_type();
+                let tokens = ton_token_unpacker::json::deserialize_tokens(&params, deserializer)?;
+                ton_token_unpacker::UnpackToken::<#ident>::unpack(tokens)
+                    .map_err(<D::Error as ton_token_unpacker::serde::de::Error>::custom)
+            }
+        } else {
+            quote! {
+                let param_type = <#ident as ton_token_unpacker::KnownParamType>::param_type();
+                let value = ton_token_unpacker::json::deserialize_token_value(&param_type, deserializer)?;
+                ton_token_unpacker::UnpackToken::<#ident>::unpack(value)
+                    .map_err(<D::Error as ton_token_unpacker::serde::de::Error>::custom)
+            }
+        };
+        result = quote! {
+            #result
+
+            impl<'de> ton_token_unpacker::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+                where
+                    D: ton_token_unpacker::serde::Deserializer<'de>,
+                {
+                    #deserialize
+                }
+            }
+        };
+    }
+
+    Ok(with_crate_path(
+        container.attrs.crate_path.as_ref(),
+        &["ton_abi", "ton_token_unpacker"],
+        result,
+    ))
 }
 
 enum StructType {
     Tuple,
     Plain,
+    Values,
 }
 
 fn serialize_enum(container: &Container, variants: &[Variant]) -> proc_macro2::TokenStream {
     let name = &container.ident;
+    let repr = container.attrs.repr.unwrap_or_default();
 
     let build_variants = variants
         .iter()
-        .filter_map(|variant| {
-            variant
-                .original
-                .discriminant
-                .as_ref()
-                .map(|(_, discriminant)| (variant.ident.clone(), discriminant))
-        })
-        .map(|(ident, discriminant)| {
-            let token = quote::ToTokens::to_token_stream(discriminant).to_string();
-            let number = token.parse::<u8>().unwrap();
+        .filter(|variant| !variant.attrs.other)
+        .map(|variant| {
+            let ident = &variant.ident;
+            let tag = match repr {
+                EnumRepr::Uint(_) => Literal::u64_suffixed(variant.discriminant as u64),
+                EnumRepr::Int(_) => Literal::i64_suffixed(variant.discriminant as i64),
+                EnumRepr::String | EnumRepr::Bytes => Literal::string(&variant_tag(variant)),
+            };
 
             quote! {
-                Some(#number) => Ok(#name::#ident)
+                Some(#tag) => Ok(#name::#ident)
             }
         });
 
+    let other_variant = match variants.iter().find(|variant| variant.attrs.other) {
+        Some(variant) => {
+            let ident = &variant.ident;
+            let value = if repr.is_text() {
+                quote!(value.to_string())
+            } else {
+                quote! {
+                    std::convert::TryFrom::try_from(value)
+                        .map_err(|_| ton_token_unpacker::UnpackerError::InvalidAbi)?
+                }
+            };
+            quote! {
+                Some(value) => Ok(#name::#ident(#value)),
+            }
+        }
+        None => quote!(),
+    };
+
+    let tag = match repr {
+        EnumRepr::Uint(_) => quote! {
+            ton_abi::TokenValue::Uint(int) => ton_token_unpacker::num_traits::ToPrimitive::to_u64(&int.number),
+        },
+        EnumRepr::Int(_) => quote! {
+            ton_abi::TokenValue::Int(int) => ton_token_unpacker::num_traits::ToPrimitive::to_i64(&int.number),
+        },
+        EnumRepr::String => quote! {
+            ton_abi::TokenValue::String(value) => Some(value),
+        },
+        EnumRepr::Bytes => quote! {
+            ton_abi::TokenValue::Bytes(value) => std::string::String::from_utf8(value).ok(),
+        },
+    };
+    let matched_tag = if repr.is_text() {
+        quote!(tag.as_deref())
+    } else {
+        quote!(tag)
+    };
+
     quote! {
-        match self {
-            ton_abi::TokenValue::Uint(int) => match ton_token_unpacker::num_traits::ToPrimitive::to_u8(&int.number) {
-                #(#build_variants,)*
-                _ => Err(ton_token_unpacker::UnpackerError::InvalidAbi),
-            },
+        let tag = match self {
+            #tag
+            _ => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
+        };
+
+        match #matched_tag {
+            #(#build_variants,)*
+            #other_variant
             _ => Err(ton_token_unpacker::UnpackerError::InvalidAbi),
         }
     }
@@ -101,29 +202,84 @@ fn serialize_struct(
     let build_fields = fields.iter().map(|f| {
         let name = f.original.ident.as_ref().unwrap();
 
-        if is_abi(&f.original.attrs) {
+        if f.attrs.flatten {
+            match struct_type {
+                StructType::Values => quote! {
+                    #name: ton_token_unpacker::UnpackTokens::unpack_token_values(tokens.by_ref())?
+                },
+                _ => quote! {
+                    #name: ton_token_unpacker::UnpackTokens::unpack_tokens(tokens.by_ref())?
+                },
+            }
+        } else if is_abi(&f.original.attrs) {
             let field_name = match &f.attrs.name {
                 Some(v) => v.clone(),
                 None => name.to_string(),
             };
 
-            let try_unpack = try_unpack(&f.attrs.type_name, &f.attrs.unpack_with);
+            let try_unpack = match &f.attrs.cell_of {
+                Some(cell_of) => quote! {
+                    match value {
+                        Some(ton_abi::TokenValue::Cell(cell)) => {
+                            ton_token_unpacker::cell::unpack_cell::<#cell_of>(
+                                cell,
+                                ton_token_unpacker::cell::ABI_VERSION,
+                            )?
+                        }
+                        _ => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
+                    }
+                },
+                None => try_unpack(
+                    f.ty,
+                    &f.attrs.type_name,
+                    &f.attrs.unpack_with,
+                    &f.attrs.with,
+                ),
+            };
+            let custom = f.attrs.unpack_with.is_some() || f.attrs.with.is_some();
+            let try_unpack = if f.attrs.type_name.is_some() && !custom && is_smart_pointer(f.ty) {
+                quote! {
+                    ton_token_unpacker::abi_type::FromInner::from_inner(#try_unpack)
+                }
+            } else {
+                try_unpack
+            };
+
+            let next_value = match struct_type {
+                StructType::Values => quote! {
+                    tokens.next()
+                },
+                _ => quote! {
+                    match tokens.next() {
+                        Some(token) if token.name == #field_name => Some(token.value),
+                        Some(token) => {
+                            return Err(ton_token_unpacker::UnpackerError::InvalidName {
+                                expected: #field_name.to_string(),
+                                found: token.name,
+                            })
+                        }
+                        None => None,
+                    }
+                },
+            };
+
+            let unwrap_ref = if f.attrs.is_ref {
+                quote! {
+                    let value = match value {
+                        Some(ton_abi::TokenValue::Ref(value)) => Some(*value),
+                        Some(_) => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
+                        None => None,
+                    };
+                }
+            } else {
+                quote!()
+            };
 
             quote! {
                 #name: {
-                    let token = tokens.next();
-                    let name = match &token {
-                        Some(token) => token.name.clone(),
-                        None => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
-                    };
-                    if name == #field_name {
-                        #try_unpack
-                    } else {
-                        return Err(ton_token_unpacker::UnpackerError::InvalidName{
-                            expected: #field_name.to_string(),
-                            found: name,
-                        });
-                    }
+                    let value = #next_value;
+                    #unwrap_ref
+                    #try_unpack
                 }
             }
         } else {
@@ -134,10 +290,8 @@ fn serialize_struct(
     });
 
     match struct_type {
-        StructType::Plain => {
+        StructType::Plain | StructType::Values => {
             quote! {
-                let mut tokens = self.into_iter();
-
                 std::result::Result::Ok(#name {
                     #(#build_fields,)*
                 })
@@ -159,138 +313,42 @@ fn serialize_struct(
 }
 
 fn try_unpack(
+    ty: &syn::Type,
     type_name: &Option<TypeName>,
     unpack_with: &Option<syn::Expr>,
+    with: &Option<syn::Path>,
 ) -> proc_macro2::TokenStream {
-    match unpack_with {
-        Some(data) => quote! {
-            match token {
-                Some(token) => #data(&token.value)?,
+    match (unpack_with, with) {
+        (Some(data), _) => quote! {
+            match value {
+                Some(value) => #data(&value)?,
+                None => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
+            }
+        },
+        (None, Some(with)) => quote! {
+            match value {
+                Some(value) => #with::unpack(&value)?,
                 None => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
             }
         },
-        None => match type_name {
+        (None, None) => match type_name {
             Some(type_name) => {
-                let handler = get_handler(type_name);
+                let marker = type_name_marker(quote!(ton_token_unpacker), type_name);
+                let from_abi = quote_spanned! {ty.span()=>
+                    <_ as ton_token_unpacker::abi_type::FromAbi<#marker>>::from_abi
+                };
                 quote! {
-                    match token {
-                        Some(token) => {
-                            match token.value {
-                                #handler
-                                _ => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
-                            }
-                        },
+                    match value {
+                        Some(value) => #from_abi(value)?,
                         None => return Err(ton_token_unpacker::UnpackerError::InvalidAbi),
                     }
                 }
             }
             None => {
                 quote! {
-                    token.unpack()?
+                    value.unpack()?
                 }
             }
         },
     }
 }
-
-fn get_handler(type_name: &TypeName) -> proc_macro2::TokenStream {
-    match type_name {
-        TypeName::Int8 => {
-            quote! {
-                ton_abi::TokenValue::Int(ton_abi::Int { number: value, size: 8 }) => {
-                    ton_token_unpacker::num_traits::ToPrimitive::to_i8(&value)
-                    .ok_or(ton_token_unpacker::UnpackerError::InvalidAbi)?
-                },
-            }
-        }
-        TypeName::Uint8 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: value, size: 8 }) => {
-                    ton_token_unpacker::num_traits::ToPrimitive::to_u8(&value)
-                    .ok_or(ton_token_unpacker::UnpackerError::InvalidAbi)?
-                },
-            }
-        }
-        TypeName::Uint16 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: value, size: 16 }) => {
-                    ton_token_unpacker::num_traits::ToPrimitive::to_u16(&value)
-                    .ok_or(ton_token_unpacker::UnpackerError::InvalidAbi)?
-                },
-            }
-        }
-        TypeName::Uint32 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: value, size: 32 }) => {
-                    ton_token_unpacker::num_traits::ToPrimitive::to_u32(&value)
-                    .ok_or(ton_token_unpacker::UnpackerError::InvalidAbi)?
-                },
-            }
-        }
-        TypeName::Uint64 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: value, size: 64 }) => {
-                    ton_token_unpacker::num_traits::ToPrimitive::to_u64(&value)
-                    .ok_or(ton_token_unpacker::UnpackerError::InvalidAbi)?
-                },
-            }
-        }
-        TypeName::Uint128 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: value, size: 128 }) => {
-                    ton_token_unpacker::num_traits::ToPrimitive::to_u128(&value)
-                    .ok_or(ton_token_unpacker::UnpackerError::InvalidAbi)?
-                },
-            }
-        }
-        TypeName::Uint160 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: value, size: 160 }) => {
-                    value
-                },
-            }
-        }
-        TypeName::Uint256 => {
-            quote! {
-                ton_abi::TokenValue::Uint(data) => {
-                    let bytes = data.number.to_bytes_be();
-
-                    let mut result = [0; 32];
-                    let len = std::cmp::min(bytes.len(), 32);
-                    let offset = 32 - len;
-                    (0..len).for_each(|i| result[i + offset] = bytes[i]);
-
-                    result.into()
-                },
-            }
-        }
-        TypeName::Address => {
-            quote! {
-                ton_abi::TokenValue::Address(ton_block::MsgAddress::AddrStd(addr)) => {
-                    ton_block::MsgAddressInt::AddrStd(addr)
-                },
-                ton_abi::TokenValue::Address(ton_block::MsgAddress::AddrVar(addr)) => {
-                    ton_block::MsgAddressInt::AddrVar(addr)
-                },
-            }
-        }
-        TypeName::Cell => {
-            quote! {
-                ton_abi::TokenValue::Cell(cell) => cell,
-            }
-        }
-        TypeName::Bool => {
-            quote! {
-                ton_abi::TokenValue::Bool(value) => value,
-            }
-        }
-        TypeName::Biguint128 => {
-            quote! {
-                ton_abi::TokenValue::Uint(ton_abi::Uint { number: value, size: 128 }) => {
-                    value
-                },
-            }
-        }
-        TypeName::None => unreachable!(),
-    }
-}
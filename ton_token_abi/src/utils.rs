@@ -1,3 +1,8 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::ast::Variant;
+use crate::attr::TypeName;
 use crate::symbol::ABI;
 
 pub fn is_abi(attrs: &[syn::Attribute]) -> bool {
@@ -9,3 +14,72 @@ pub fn is_abi(attrs: &[syn::Attribute]) -> bool {
 
     false
 }
+
+/// Whether the type is `Box`, `Rc`, `Arc` or `Cow`, which are transparent around `#[abi(type)]` fields.
+pub fn is_smart_pointer(ty: &syn::Type) -> bool {
+    let segment = match ty {
+        syn::Type::Path(ty) if ty.qself.is_none() => match ty.path.segments.last() {
+            Some(segment) => segment,
+            None => return false,
+        },
+        _ => return false,
+    };
+    matches!(segment.arguments, syn::PathArguments::AngleBracketed(_))
+        && ["Box", "Rc", "Arc", "Cow"]
+            .iter()
+            .any(|name| segment.ident == name)
+}
+
+pub fn variant_tag(variant: &Variant) -> String {
+    match &variant.attrs.rename {
+        Some(rename) => rename.clone(),
+        None => variant.ident.to_string(),
+    }
+}
+
+/// Resolves runtime crates through the path given in `#[abi(crate = "...")]`.
+///
+/// Generated code refers to the `crates` by name, so it is wrapped into an
+/// anonymous const which imports them from the facade crate.
+pub fn with_crate_path(
+    crate_path: Option<&syn::Path>,
+    crates: &[&str],
+    tokens: TokenStream,
+) -> TokenStream {
+    let crate_path = match crate_path {
+        Some(crate_path) => crate_path,
+        None => return tokens,
+    };
+    let crates = crates
+        .iter()
+        .map(|name| Ident::new(name, Span::call_site()));
+
+    quote! {
+        const _: () = {
+            #(use #crate_path::#crates;)*
+
+            #tokens
+        };
+    }
+}
+
+/// Path to the marker type of an `#[abi(type)]` attribute, re-exported by the runtime crate `krate`.
+pub fn type_name_marker(krate: TokenStream, type_name: &TypeName) -> TokenStream {
+    let marker = match type_name {
+        TypeName::Int8 => "Int8",
+        TypeName::Uint8 => "Uint8",
+        TypeName::Uint16 => "Uint16",
+        TypeName::Uint32 => "Uint32",
+        TypeName::Uint64 => "Uint64",
+        TypeName::Uint128 => "Uint128",
+        TypeName::Uint160 => "Uint160",
+        TypeName::Uint256 => "Uint256",
+        TypeName::Biguint128 => "Biguint128",
+        TypeName::Bool => "Bool",
+        TypeName::Cell => "Cell",
+        TypeName::Address => "Address",
+        TypeName::None => unreachable!(),
+    };
+    let marker = Ident::new(marker, Span::call_site());
+    quote!(#krate::abi_type::#marker)
+}
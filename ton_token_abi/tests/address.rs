@@ -0,0 +1,93 @@
+use std::str::FromStr;
+
+use ton_abi::{Token, TokenValue};
+use ton_block::{MsgAddrExt, MsgAddress, MsgAddressInt};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::{AddressError, FriendlyAddress, PackerError, TryPackTokens};
+use ton_token_unpacker::{UnpackToken, UnpackTokens};
+
+const RAW: &str = "0:a921453472366b7feeec15323a96b5dcf17197c88dc0d4578dfa52900b8a33cb";
+const BOUNCEABLE: &str = "EQCpIUU0cjZrf-7sFTI6lrXc8XGXyI3A1FeN-lKQC4ozy35r";
+const NON_BOUNCEABLE: &str = "UQCpIUU0cjZrf+7sFTI6lrXc8XGXyI3A1FeN+lKQC4ozyyOu";
+
+#[derive(PackAbi, UnpackAbi)]
+#[abi(plain, checked)]
+struct Data {
+    #[abi(address)]
+    owner: Option<MsgAddressInt>,
+    #[abi]
+    manager: Option<MsgAddressInt>,
+    #[abi(address)]
+    recipient: FriendlyAddress,
+    #[abi(address)]
+    any: MsgAddress,
+    #[abi]
+    external: MsgAddrExt,
+    #[abi(address)]
+    sender: String,
+    #[abi(address)]
+    referrer: Option<String>,
+}
+
+fn main() {
+    let address = MsgAddressInt::from_str(RAW).unwrap();
+    assert_eq!(ton_token_packer::parse_address(RAW).unwrap(), address);
+    assert_eq!(
+        ton_token_packer::parse_address(BOUNCEABLE).unwrap(),
+        address
+    );
+    assert_eq!(
+        ton_token_packer::parse_address(NON_BOUNCEABLE).unwrap(),
+        address
+    );
+    assert_eq!(
+        ton_token_packer::parse_address("EQCpIUU0cjZrf-7sFTI6lrXc8XGXyI3A1FeN-lKQC4ozy35s"),
+        Err(AddressError::InvalidChecksum)
+    );
+
+    let data = Data {
+        owner: None,
+        manager: Some(address.clone()),
+        recipient: FriendlyAddress::from_str(BOUNCEABLE).unwrap(),
+        any: MsgAddress::AddrNone,
+        external: MsgAddrExt::default(),
+        sender: NON_BOUNCEABLE.to_string(),
+        referrer: None,
+    };
+
+    let tokens = data.try_pack().unwrap();
+    assert_eq!(tokens[0].value, TokenValue::Address(MsgAddress::AddrNone));
+    assert_eq!(tokens[3].value, TokenValue::Address(MsgAddress::AddrNone));
+    assert!(matches!(
+        tokens[4].value,
+        TokenValue::Address(MsgAddress::AddrExt(_))
+    ));
+
+    let data = Data::unpack_tokens(&mut tokens.into_iter()).unwrap();
+    assert_eq!(data.owner, None);
+    assert_eq!(data.manager, Some(address));
+    assert_eq!(data.recipient.to_string(), BOUNCEABLE);
+    assert_eq!(format!("{:#}", data.recipient), RAW);
+    assert_eq!(data.any, MsgAddress::AddrNone);
+    assert_eq!(data.external, MsgAddrExt::default());
+    assert_eq!(data.sender, RAW);
+    assert_eq!(data.referrer, None);
+
+    let data = Data {
+        owner: None,
+        manager: None,
+        recipient: FriendlyAddress::from_str(BOUNCEABLE).unwrap(),
+        any: MsgAddress::AddrNone,
+        external: MsgAddrExt::default(),
+        sender: RAW.to_string(),
+        referrer: Some("not an address".to_string()),
+    };
+    match data.try_pack() {
+        Err(PackerError::InvalidValue { path, .. }) => assert_eq!(path, "referrer"),
+        _ => panic!("expected invalid value"),
+    }
+
+    let none = Token::new("owner", TokenValue::Address(MsgAddress::AddrNone));
+    let result: Result<MsgAddressInt, _> = none.unpack();
+    assert!(result.is_err());
+}
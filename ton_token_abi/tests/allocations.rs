@@ -0,0 +1,85 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ton_abi::TokenValue;
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::cell::{build_cell, ABI_VERSION};
+use ton_token_packer::store::BuilderData;
+use ton_token_packer::{BuildTokenValue, StoreBuilder};
+use ton_token_unpacker::UnpackToken;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(PackAbi, UnpackAbi, Debug, PartialEq)]
+#[abi(store)]
+struct Item {
+    #[abi(uint8)]
+    kind: u8,
+    #[abi(uint16)]
+    flags: u16,
+    #[abi(uint32)]
+    index: u32,
+    #[abi(uint64)]
+    amount: u64,
+    #[abi(uint128)]
+    total: u128,
+    #[abi]
+    value: u32,
+    #[abi(bool)]
+    flag: bool,
+}
+
+fn item(i: u32) -> Item {
+    Item {
+        kind: i as u8,
+        flags: i as u16,
+        index: i,
+        amount: i as u64 * 1000,
+        total: i as u128 * 1_000_000,
+        value: i + 1,
+        flag: i % 2 == 0,
+    }
+}
+
+fn main() {
+    let values: Vec<TokenValue> = (0..1000).map(|i| item(i).token_value()).collect();
+    let mut items: Vec<Item> = Vec::with_capacity(values.len());
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    for value in values {
+        items.push(value.unpack().unwrap());
+    }
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+
+    assert_eq!(after - before, 0);
+    assert!(items
+        .iter()
+        .zip(0..)
+        .all(|(unpacked, i)| *unpacked == item(i)));
+
+    // Storing skips tokens, it must write the same bits as the ABI encoder
+    for item in items {
+        let mut builder = BuilderData::new();
+        item.store_into(&mut builder).unwrap();
+        assert_eq!(
+            builder.into_cell().unwrap(),
+            build_cell(item, ABI_VERSION).unwrap()
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+use ton_abi::{ParamType, TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::{AmountError, BuildTokenValue, TokenAmount};
+use ton_token_unpacker::{KnownParamType, UnpackToken};
+
+type Tokens = TokenAmount<9>;
+
+#[derive(PackAbi, UnpackAbi)]
+struct Wallet {
+    #[abi]
+    balance: Tokens,
+    #[abi(with = "ton_token::with::varuint16")]
+    fee: Tokens,
+}
+
+fn main() {
+    let amount = Tokens::from_str("12.345").unwrap();
+    assert_eq!(amount.units(), 12_345_000_000);
+    assert_eq!(amount.to_string(), "12.345");
+    assert_eq!(Tokens::from_str("0.000000001").unwrap().units(), 1);
+    assert_eq!(
+        Tokens::from_str("7").unwrap(),
+        Tokens::from_tokens(7).unwrap()
+    );
+    assert_eq!(Tokens::from_units(7_000_000_000).to_string(), "7");
+    assert_eq!(TokenAmount::<0>::from_units(42).to_string(), "42");
+    assert_eq!(
+        TokenAmount::<18>::from_str("340282366920938463463.374607431768211455")
+            .unwrap()
+            .units(),
+        u128::MAX
+    );
+
+    assert_eq!(
+        Tokens::from_str("0.0000000001"),
+        Err(AmountError::TooManyDecimals(9))
+    );
+    assert_eq!(Tokens::from_str(".5"), Err(AmountError::InvalidFormat));
+    assert_eq!(Tokens::from_str("1."), Err(AmountError::InvalidFormat));
+    assert_eq!(Tokens::from_str("."), Err(AmountError::InvalidFormat));
+    assert_eq!(Tokens::from_str("-1"), Err(AmountError::InvalidFormat));
+    assert_eq!(
+        TokenAmount::<18>::from_str("340282366920938463464"),
+        Err(AmountError::Overflow)
+    );
+
+    let one = Tokens::from_tokens(1).unwrap();
+    assert_eq!(amount.checked_add(one).unwrap().to_string(), "13.345");
+    assert_eq!(one.checked_sub(amount), None);
+    assert_eq!(amount.checked_mul(2).unwrap().to_string(), "24.69");
+    assert_eq!(amount.checked_div(0), None);
+    assert_eq!(
+        Tokens::from_units(u128::MAX).checked_add(Tokens::from_units(1)),
+        None
+    );
+
+    let wallet = Wallet {
+        balance: amount,
+        fee: Tokens::from_str("0.05").unwrap(),
+    };
+    let value = wallet.token_value();
+    match &value {
+        TokenValue::Tuple(tokens) => {
+            assert_eq!(
+                tokens[0].value,
+                TokenValue::Uint(Uint::new(12_345_000_000, 128))
+            );
+            assert!(matches!(tokens[1].value, TokenValue::VarUint(16, _)));
+        }
+        _ => panic!("expected tuple"),
+    }
+
+    let wallet: Wallet = value.unpack().unwrap();
+    assert_eq!(wallet.balance, amount);
+    assert_eq!(wallet.fee.to_string(), "0.05");
+
+    assert_eq!(
+        <Tokens as KnownParamType>::param_type(),
+        ParamType::Uint(128)
+    );
+}
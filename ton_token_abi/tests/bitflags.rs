@@ -0,0 +1,69 @@
+use bitflags::bitflags;
+use ton_abi::{Token, TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::{UnpackToken, UnpackerError};
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Confirmations: u32 {
+        const FIRST = 0b001;
+        const SECOND = 0b010;
+        const THIRD = 0b100;
+    }
+}
+
+ton_token_packer::impl_bitflags!(Confirmations);
+ton_token_unpacker::impl_bitflags!(Confirmations);
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Permissions: u8 {
+        const READ = 0b01;
+        const WRITE = 0b10;
+    }
+}
+
+ton_token_packer::impl_bitflags!(Permissions);
+ton_token_unpacker::impl_bitflags!(Permissions, strict);
+
+#[derive(PackAbi, UnpackAbi)]
+struct Transaction {
+    #[abi(name = "confirmationsMask")]
+    confirmations_mask: Confirmations,
+    #[abi(with = "ton_token::with::bitflags_strict")]
+    permissions: Permissions,
+}
+
+fn main() {
+    let mask = Confirmations::FIRST | Confirmations::THIRD;
+    assert_eq!(mask.token_value(), TokenValue::Uint(Uint::new(5, 32)));
+
+    let parsed: Confirmations = TokenValue::Uint(Uint::new(0b1010, 32)).unpack().unwrap();
+    assert_eq!(parsed.bits(), 0b1010);
+    assert!(parsed.contains(Confirmations::SECOND));
+
+    let parsed: Permissions = TokenValue::Uint(Uint::new(0b11, 8)).unpack().unwrap();
+    assert_eq!(parsed, Permissions::READ | Permissions::WRITE);
+    let parsed: Result<Permissions, UnpackerError> = TokenValue::Uint(Uint::new(0b100, 8)).unpack();
+    assert!(parsed.is_err());
+
+    let masks = vec![Confirmations::FIRST, Confirmations::SECOND];
+    let parsed: Vec<Confirmations> = masks.clone().token_value().unpack().unwrap();
+    assert_eq!(parsed, masks);
+
+    let transaction = Transaction {
+        confirmations_mask: Confirmations::SECOND,
+        permissions: Permissions::WRITE,
+    };
+    let parsed: Transaction = transaction.token_value().unpack().unwrap();
+    assert_eq!(parsed.confirmations_mask, Confirmations::SECOND);
+    assert_eq!(parsed.permissions, Permissions::WRITE);
+
+    let tokens = vec![
+        Token::new("confirmationsMask", TokenValue::Uint(Uint::new(1, 32))),
+        Token::new("permissions", TokenValue::Uint(Uint::new(0xff, 8))),
+    ];
+    let parsed: Result<Transaction, UnpackerError> = TokenValue::Tuple(tokens).unpack();
+    assert!(parsed.is_err());
+}
@@ -0,0 +1,45 @@
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::cell::{to_boc_base64, to_cell};
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::cell::{from_boc_base64, from_cell};
+use ton_token_unpacker::{UnpackToken, UnpackerError};
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, Clone, PartialEq)]
+#[abi(plain)]
+struct Transfer {
+    #[abi(uint32)]
+    nonce: u32,
+    #[abi]
+    amount: u128,
+    #[abi]
+    items: Vec<Item>,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, Clone, PartialEq)]
+struct Item {
+    #[abi(bool)]
+    flag: bool,
+}
+
+const ABI_VERSION: u8 = 2;
+
+fn main() {
+    let transfer = Transfer {
+        nonce: 7,
+        amount: 1_000_000_000,
+        items: vec![Item { flag: true }, Item { flag: false }],
+    };
+
+    let cell = to_cell(&transfer, ABI_VERSION).unwrap();
+    let decoded: Transfer = from_cell(cell, ABI_VERSION).unwrap();
+    assert_eq!(decoded, transfer);
+
+    let boc = to_boc_base64(&transfer, ABI_VERSION).unwrap();
+    let decoded: Transfer = from_boc_base64(&boc, ABI_VERSION).unwrap();
+    assert_eq!(decoded, transfer);
+
+    assert!(matches!(
+        from_boc_base64::<Transfer>("not a boc!", ABI_VERSION),
+        Err(UnpackerError::InvalidCell(_))
+    ));
+}
@@ -0,0 +1,47 @@
+use std::str::FromStr;
+
+use ton_abi::{Param, ParamType, TokenValue};
+use ton_block::MsgAddressInt;
+use ton_token_packer::{BuilderError, TokensBuilder};
+
+fn main() {
+    let addr = MsgAddressInt::from_str(
+        "0:18c99afffe13d3081370f77c10fc4d51bc54e52b8e181db6a0e8bb75456d91ff",
+    )
+    .unwrap();
+
+    let tokens = TokensBuilder::new()
+        .uint(32, "nonce", 5)
+        .address("dest", addr.clone())
+        .value("flag", true)
+        .build();
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(tokens[0].name, "nonce");
+    assert_eq!(tokens[0].value, TokenValue::Uint(ton_abi::Uint::new(5, 32)));
+    assert_eq!(tokens[2].value, TokenValue::Bool(true));
+
+    let params = vec![
+        Param::new("nonce", ParamType::Uint(32)),
+        Param::new("dest", ParamType::Address),
+    ];
+
+    let tokens = TokensBuilder::for_params(&params)
+        .uint(32, "nonce", 5)
+        .address("dest", addr.clone())
+        .try_build()
+        .unwrap();
+    assert_eq!(tokens.len(), 2);
+
+    let error = TokensBuilder::for_params(&params)
+        .uint(32, "nonce", 5)
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(error, BuilderError::MissingToken { name } if name == "dest"));
+
+    let error = TokensBuilder::for_params(&params)
+        .address("dest", addr)
+        .uint(32, "nonce", 5)
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(error, BuilderError::InvalidName { .. }));
+}
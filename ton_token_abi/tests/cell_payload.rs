@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use ton_abi::{Token, TokenValue};
+use ton_block::MsgAddressInt;
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::PackTokens;
+use ton_token_unpacker::{CellPayload, UnpackToken, UnpackTokens};
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, Clone, PartialEq)]
+struct Payload {
+    #[abi(uint32)]
+    action: u32,
+    #[abi(address)]
+    recipient: MsgAddressInt,
+}
+
+#[derive(PackAbi, UnpackAbi)]
+#[abi(plain)]
+struct TransferNotification {
+    #[abi(uint128)]
+    amount: u128,
+    #[abi(cell)]
+    payload: CellPayload<Payload>,
+}
+
+fn main() {
+    let payload = Payload {
+        action: 2,
+        recipient: MsgAddressInt::from_str(
+            "0:a921453472366b7feeec15323a96b5dcf17197c88dc0d4578dfa52900b8a33cb",
+        )
+        .unwrap(),
+    };
+    let notification = TransferNotification {
+        amount: 100,
+        payload: CellPayload::new(payload.clone()).unwrap(),
+    };
+
+    let tokens = notification.pack();
+    assert!(matches!(tokens[1].value, TokenValue::Cell(_)));
+
+    let unpacked = TransferNotification::unpack_tokens(&mut tokens.into_iter()).unwrap();
+    assert_eq!(unpacked.amount, 100);
+    assert_eq!(unpacked.payload.decode().unwrap(), payload);
+
+    let raw = Token::new("payload", TokenValue::Cell(unpacked.payload.into_cell()));
+    let payload: CellPayload<Payload> = raw.unpack().unwrap();
+    assert_eq!(payload.decode().unwrap().action, 2);
+}
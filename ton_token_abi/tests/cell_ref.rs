@@ -0,0 +1,93 @@
+use num_bigint::BigUint;
+use ton_abi::{Param, ParamType, TokenValue};
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::{BuildTokenValue, PackerError, TryBuildTokenValue};
+use ton_token_unpacker::{CellPayload, KnownParamType, UnpackToken};
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, Clone, PartialEq)]
+struct Metadata {
+    #[abi]
+    decimals: u8,
+    #[abi]
+    supply: u128,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, PartialEq)]
+struct Root {
+    #[abi(ref)]
+    metadata: Metadata,
+    #[abi(ref, uint32)]
+    seqno: u32,
+    #[abi(name = "storedMetadata", cell_of = "Metadata")]
+    stored_metadata: Metadata,
+}
+
+#[derive(PackAbi, Clone)]
+struct Balance {
+    #[abi(biguint128)]
+    value: BigUint,
+}
+
+#[derive(PackAbi)]
+struct Wallet {
+    #[abi(cell_of = "Balance")]
+    balance: Balance,
+}
+
+fn main() {
+    let metadata = Metadata {
+        decimals: 9,
+        supply: 1_000_000,
+    };
+    let root = Root {
+        metadata: metadata.clone(),
+        seqno: 3,
+        stored_metadata: metadata.clone(),
+    };
+
+    let value = root.token_value();
+    match &value {
+        TokenValue::Tuple(tokens) => {
+            assert!(matches!(tokens[0].value, TokenValue::Ref(_)));
+            assert!(matches!(tokens[1].value, TokenValue::Ref(_)));
+            assert!(matches!(tokens[2].value, TokenValue::Cell(_)));
+        }
+        _ => panic!("expected tuple"),
+    }
+
+    let unpacked: Root = value.unpack().unwrap();
+    assert_eq!(unpacked.metadata, metadata);
+    assert_eq!(unpacked.seqno, 3);
+    assert_eq!(unpacked.stored_metadata, metadata);
+
+    let not_ref: Result<Root, _> = TokenValue::Tuple(vec![
+        ton_abi::Token::new("metadata", metadata.clone().token_value()),
+        ton_abi::Token::new("seqno", 3u32.token_value()),
+    ])
+    .unpack();
+    assert!(not_ref.is_err());
+
+    assert_eq!(
+        Root::param_type(),
+        ParamType::Tuple(vec![
+            Param::new("metadata", ParamType::Ref(Box::new(Metadata::param_type()))),
+            Param::new("seqno", ParamType::Ref(Box::new(ParamType::Uint(32)))),
+            Param::new("storedMetadata", ParamType::Cell),
+        ])
+    );
+
+    let balance = Balance {
+        value: BigUint::from(1u8) << 128,
+    };
+    let wallet = Wallet {
+        balance: balance.clone(),
+    };
+    assert!(matches!(
+        wallet.try_token_value(),
+        Err(PackerError::IntegerOverflow { path, size: 128 }) if path == "balance.value"
+    ));
+    assert!(matches!(
+        CellPayload::new(balance),
+        Err(PackerError::IntegerOverflow { path, size: 128 }) if path == "value"
+    ));
+}
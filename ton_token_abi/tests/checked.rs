@@ -0,0 +1,120 @@
+use num_bigint::BigUint;
+use ton_abi::{TokenValue, Uint};
+use ton_token_abi::PackAbi;
+use ton_token_packer::{BuildTokenValue, PackerError, TryBuildTokenValue, TryPackTokens};
+
+#[derive(PackAbi, Clone)]
+struct Wallet {
+    #[abi(uint160)]
+    id: BigUint,
+    #[abi]
+    balance: u128,
+}
+
+#[derive(PackAbi, Clone)]
+#[abi(plain)]
+struct Transfer {
+    #[abi]
+    wallet: Wallet,
+    #[abi(biguint128)]
+    amount: BigUint,
+}
+
+#[derive(PackAbi, Clone)]
+#[abi(checked)]
+struct Batch {
+    #[abi]
+    wallets: Vec<Wallet>,
+    #[abi(biguint128)]
+    total: BigUint,
+}
+
+fn main() {
+    assert_eq!(
+        u128::MAX.token_value(),
+        TokenValue::Uint(Uint::new(u128::MAX, 128))
+    );
+
+    let wallet = Wallet {
+        id: BigUint::from(1u8) << 159,
+        balance: u128::MAX,
+    };
+    assert!(wallet.clone().try_token_value().is_ok());
+
+    let transfer = Transfer {
+        wallet: Wallet {
+            id: BigUint::from(1u8) << 160,
+            balance: 1,
+        },
+        amount: BigUint::from(1u8),
+    };
+    match transfer.try_pack() {
+        Err(PackerError::IntegerOverflow { path, size }) => {
+            assert_eq!(path, "wallet.id");
+            assert_eq!(size, 160);
+        }
+        _ => panic!("expected overflow"),
+    }
+
+    let transfer = Transfer {
+        wallet,
+        amount: BigUint::from(1u8) << 128,
+    };
+    let error = transfer.try_pack().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Value of `amount` doesn't fit into 128 bits"
+    );
+
+    let error = vec![TokenValue::Uint(Uint::new(256, 8))]
+        .try_token_value()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Value of `[0]` doesn't fit into 8 bits");
+
+    let error = TokenValue::VarUint(0, BigUint::from(0u8))
+        .try_token_value()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Value of `<root>` doesn't fit into 0 bits"
+    );
+
+    let error = TokenValue::Uint(Uint::new(0, 0))
+        .try_token_value()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Value of `<root>` doesn't fit into 0 bits"
+    );
+
+    let batch = Batch {
+        wallets: vec![
+            Wallet {
+                id: BigUint::from(1u8),
+                balance: 1,
+            },
+            Wallet {
+                id: BigUint::from(1u8) << 160,
+                balance: 1,
+            },
+        ],
+        total: BigUint::from(2u8),
+    };
+    match batch.clone().try_token_value() {
+        Err(PackerError::IntegerOverflow { path, size }) => {
+            assert_eq!(path, "wallets[1].id");
+            assert_eq!(size, 160);
+        }
+        _ => panic!("expected overflow"),
+    }
+
+    let batch = Batch {
+        wallets: Vec::new(),
+        total: BigUint::from(1u8) << 128,
+    };
+    let error = batch.try_token_value().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Value of `total` doesn't fit into 128 bits"
+    );
+}
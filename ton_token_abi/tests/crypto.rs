@@ -0,0 +1,71 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use ton_abi::{Token, TokenValue};
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::{BuildTokenValue, CryptoError, PackTokens, PublicKey, Signature};
+use ton_token_unpacker::{KnownParamTypePlain, UnpackToken, UnpackTokens, UnpackerError};
+
+// RFC 8032, test 1
+const PUBKEY: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+#[derive(PackAbi, UnpackAbi, KnownParamType)]
+#[abi(plain)]
+struct Confirmation {
+    #[abi(uint256)]
+    custodian: PublicKey,
+    #[abi]
+    pubkey: PublicKey,
+    #[abi]
+    signature: Signature,
+}
+
+fn main() {
+    let pubkey = PublicKey::from_str(PUBKEY).unwrap();
+    assert_eq!(pubkey.to_string(), PUBKEY);
+    assert_eq!(format!("{:?}", pubkey), format!("PublicKey({})", PUBKEY));
+    assert_eq!(
+        PublicKey::from_str("d75a98"),
+        Err(CryptoError::InvalidLength {
+            expected: 32,
+            found: 3
+        })
+    );
+    assert_eq!(PublicKey::from_str("zz"), Err(CryptoError::InvalidHex));
+
+    let valid = Signature::from_str(SIGNATURE).unwrap();
+    assert!(pubkey.verify(b"", &valid));
+    assert!(!pubkey.verify(b"message", &valid));
+
+    let signature = Signature::try_from(vec![7; 64]).unwrap();
+    assert!(Signature::try_from(&[7u8; 63][..]).is_err());
+
+    let confirmation = Confirmation {
+        custodian: PublicKey::from_bytes([0; 32]),
+        pubkey,
+        signature,
+    };
+    let tokens = confirmation.pack();
+    assert!(matches!(tokens[1].value, TokenValue::Uint(_)));
+    assert_eq!(tokens[2].value, TokenValue::Bytes(vec![7; 64]));
+
+    let confirmation = Confirmation::unpack_tokens(&mut tokens.into_iter()).unwrap();
+    assert_eq!(confirmation.custodian, PublicKey::from_bytes([0; 32]));
+    assert_eq!(confirmation.pubkey, pubkey);
+    assert_eq!(confirmation.signature, signature);
+
+    let short = Token::new("signature", TokenValue::Bytes(vec![7; 10]));
+    let result: Result<Signature, _> = short.unpack();
+    assert!(matches!(
+        result,
+        Err(UnpackerError::InvalidLength {
+            expected: 64,
+            found: 10
+        })
+    ));
+
+    let params = Confirmation::param_type();
+    assert_eq!(params[1].kind, ton_abi::ParamType::Uint(256));
+    assert_eq!(params[2].kind, ton_abi::ParamType::Bytes);
+}
@@ -0,0 +1,95 @@
+use ton_abi::{TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::{BuildTokenValue, PackerError, TryBuildTokenValue};
+use ton_token_unpacker::UnpackToken;
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+#[abi(repr = "string")]
+enum Network {
+    #[abi(rename = "mainnet")]
+    Main,
+    #[abi(rename = "testnet")]
+    Test,
+    Custom,
+}
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+#[abi(repr = "bytes")]
+enum Currency {
+    #[abi(rename = "TON")]
+    Ton,
+    #[abi(rename = "USDT")]
+    Usdt,
+    #[abi(other)]
+    Unknown(String),
+}
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+enum Version {
+    V0,
+    V1,
+    V2,
+    #[abi(other)]
+    Unknown(u8),
+}
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+#[abi(repr = "uint8", checked)]
+enum Kind {
+    Simple,
+    Complex,
+    #[abi(other)]
+    Unknown(u16),
+}
+
+fn main() {
+    assert_eq!(
+        Network::Main.token_value(),
+        TokenValue::String("mainnet".to_string())
+    );
+    let parsed: Network = TokenValue::String("testnet".to_string()).unpack().unwrap();
+    assert_eq!(parsed, Network::Test);
+    let parsed: Network = TokenValue::String("Custom".to_string()).unpack().unwrap();
+    assert_eq!(parsed, Network::Custom);
+    let parsed: Result<Network, _> = TokenValue::String("devnet".to_string()).unpack();
+    assert!(parsed.is_err());
+
+    assert_eq!(
+        Currency::Usdt.token_value(),
+        TokenValue::Bytes(b"USDT".to_vec())
+    );
+    let parsed: Currency = TokenValue::Bytes(b"TON".to_vec()).unpack().unwrap();
+    assert_eq!(parsed, Currency::Ton);
+    let parsed: Currency = TokenValue::Bytes(b"EUR".to_vec()).unpack().unwrap();
+    assert_eq!(parsed, Currency::Unknown("EUR".to_string()));
+    assert_eq!(
+        Currency::Unknown("EUR".to_string()).token_value(),
+        TokenValue::Bytes(b"EUR".to_vec())
+    );
+
+    let parsed: Version = TokenValue::Uint(Uint::new(2, 8)).unpack().unwrap();
+    assert_eq!(parsed, Version::V2);
+    let parsed: Version = TokenValue::Uint(Uint::new(7, 8)).unpack().unwrap();
+    assert_eq!(parsed, Version::Unknown(7));
+    assert_eq!(
+        Version::Unknown(7).token_value(),
+        TokenValue::Uint(Uint::new(7, 8))
+    );
+    let parsed: Result<Version, _> = TokenValue::Uint(Uint::new(300, 16)).unpack();
+    assert!(parsed.is_err());
+
+    assert_eq!(
+        Kind::Unknown(2).try_token_value().unwrap(),
+        TokenValue::Uint(Uint::new(2, 8))
+    );
+    assert!(matches!(
+        Kind::Unknown(1).try_token_value(),
+        Err(PackerError::InvalidValue { .. })
+    ));
+    assert!(matches!(
+        Kind::Unknown(300).try_token_value(),
+        Err(PackerError::IntegerOverflow { size: 8, .. })
+    ));
+    let result = std::panic::catch_unwind(|| Currency::Unknown("TON".to_string()).token_value());
+    assert!(result.is_err());
+}
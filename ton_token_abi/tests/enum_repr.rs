@@ -0,0 +1,94 @@
+use ton_abi::{Int, TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::UnpackToken;
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+enum Implicit {
+    First,
+    Second,
+    Tenth = 10,
+    Eleventh,
+}
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+#[abi(repr = "uint32")]
+enum Flags {
+    Small = 1,
+    Shifted = 1 << 3,
+    Large = 0x10000,
+}
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+#[repr(u16)]
+enum Status {
+    Active = 300,
+    Inactive = 301,
+}
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+#[abi(repr = "int8")]
+enum Direction {
+    Down = -1,
+    Up = 1,
+}
+
+#[derive(PackAbi, UnpackAbi, PartialEq, Debug)]
+#[repr(u16)]
+enum Casted {
+    Wrapped = 300u16 as u8 as u16,
+    Negative = -1i8 as u8 as u16,
+}
+
+fn main() {
+    assert_eq!(
+        Implicit::First.token_value(),
+        TokenValue::Uint(Uint::new(0, 8))
+    );
+    assert_eq!(
+        Implicit::Second.token_value(),
+        TokenValue::Uint(Uint::new(1, 8))
+    );
+    assert_eq!(
+        Implicit::Eleventh.token_value(),
+        TokenValue::Uint(Uint::new(11, 8))
+    );
+    let parsed: Implicit = TokenValue::Uint(Uint::new(10, 8)).unpack().unwrap();
+    assert_eq!(parsed, Implicit::Tenth);
+
+    assert_eq!(
+        Flags::Shifted.token_value(),
+        TokenValue::Uint(Uint::new(8, 32))
+    );
+    let parsed: Flags = TokenValue::Uint(Uint::new(0x10000, 32)).unpack().unwrap();
+    assert_eq!(parsed, Flags::Large);
+    let parsed: Result<Flags, _> = TokenValue::Uint(Uint::new(2, 32)).unpack();
+    assert!(parsed.is_err());
+
+    assert_eq!(
+        Status::Inactive.token_value(),
+        TokenValue::Uint(Uint::new(301, 16))
+    );
+    let parsed: Status = TokenValue::Uint(Uint::new(300, 16)).unpack().unwrap();
+    assert_eq!(parsed, Status::Active);
+
+    assert_eq!(
+        Direction::Down.token_value(),
+        TokenValue::Int(Int::new(-1, 8))
+    );
+    let parsed: Direction = TokenValue::Int(Int::new(-1, 8)).unpack().unwrap();
+    assert_eq!(parsed, Direction::Down);
+    let parsed: Direction = Direction::Up.token_value().unpack().unwrap();
+    assert_eq!(parsed, Direction::Up);
+
+    assert_eq!(
+        Casted::Wrapped.token_value(),
+        TokenValue::Uint(Uint::new(44, 16))
+    );
+    assert_eq!(
+        Casted::Negative.token_value(),
+        TokenValue::Uint(Uint::new(255, 16))
+    );
+    let parsed: Casted = TokenValue::Uint(Uint::new(255, 16)).unpack().unwrap();
+    assert_eq!(parsed, Casted::Negative);
+}
@@ -0,0 +1,62 @@
+use ton_token::ton_abi::{Param, ParamType, TokenValue};
+use ton_token::{
+    BuildTokenValue, KnownParamType, KnownParamTypePlain, PackAbi, PackTokens, UnpackAbi,
+    UnpackToken, UnpackTokens,
+};
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, PartialEq)]
+#[abi(crate = "ton_token")]
+struct Data {
+    #[abi(uint32)]
+    value: u32,
+    #[abi]
+    status: Status,
+    #[abi(name = "items")]
+    items: Vec<Item>,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, PartialEq)]
+#[abi(crate = "ton_token")]
+struct Item {
+    #[abi(bool)]
+    flag: bool,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, PartialEq)]
+#[abi(crate = "ton_token", repr = "uint8")]
+enum Status {
+    Active = 1,
+    Inactive = 2,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, PartialEq)]
+#[abi(crate = "::ton_token", plain)]
+struct Output {
+    #[abi(uint64)]
+    time: u64,
+}
+
+fn main() {
+    let data = Data {
+        value: 42,
+        status: Status::Inactive,
+        items: vec![Item { flag: true }],
+    };
+    let value = data.token_value();
+    assert!(matches!(value, TokenValue::Tuple(_)));
+    let data: Data = value.unpack().unwrap();
+    assert_eq!(data.status, Status::Inactive);
+    assert_eq!(data.items, vec![Item { flag: true }]);
+
+    let item = ParamType::Tuple(vec![Param {
+        name: "flag".to_string(),
+        kind: ParamType::Bool,
+    }]);
+    assert_eq!(<Vec<Item>>::param_type(), ParamType::Array(Box::new(item)));
+    assert_eq!(Status::param_type(), ParamType::Uint(8));
+
+    let tokens = Output { time: 1337 }.pack();
+    assert_eq!(<Output as KnownParamTypePlain>::param_type().len(), 1);
+    let output = Output::unpack_tokens(&mut tokens.into_iter()).unwrap();
+    assert_eq!(output, Output { time: 1337 });
+}
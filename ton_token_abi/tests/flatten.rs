@@ -0,0 +1,72 @@
+use ton_abi::{Token, TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::{BuildTokenValue, PackTokens};
+use ton_token_unpacker::UnpackToken;
+
+#[derive(PackAbi, UnpackAbi)]
+#[abi(plain)]
+struct Header {
+    #[abi]
+    balance: u128,
+    #[abi(name = "totalSupply")]
+    total_supply: u128,
+}
+
+#[derive(PackAbi, UnpackAbi)]
+#[abi(plain)]
+struct WalletDetails {
+    #[abi(flatten)]
+    header: Header,
+    #[abi]
+    decimals: u8,
+}
+
+#[derive(PackAbi, UnpackAbi)]
+struct Tuple {
+    #[abi]
+    seqno: u32,
+    #[abi(flatten)]
+    header: Header,
+}
+
+fn test() -> WalletDetails {
+    let balance = Token::new("balance", TokenValue::Uint(Uint::new(100, 128)));
+    let total_supply = Token::new("totalSupply", TokenValue::Uint(Uint::new(1000, 128)));
+    let decimals = Token::new("decimals", TokenValue::Uint(Uint::new(9, 8)));
+    let tokens = vec![balance, total_supply, decimals];
+
+    let parsed: WalletDetails = tokens.unpack().unwrap();
+    parsed
+}
+
+fn main() {
+    let data = test();
+    assert_eq!(data.header.balance, 100);
+    assert_eq!(data.header.total_supply, 1000);
+    assert_eq!(data.decimals, 9);
+
+    let tokens = data.pack();
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(tokens[1].name, "totalSupply");
+    let new_data: WalletDetails = tokens.unpack().unwrap();
+    assert_eq!(new_data.header.balance, 100);
+    assert_eq!(new_data.header.total_supply, 1000);
+    assert_eq!(new_data.decimals, 9);
+
+    let tuple = Tuple {
+        seqno: 5,
+        header: Header {
+            balance: 1,
+            total_supply: 2,
+        },
+    };
+    let token = tuple.token_value();
+    match &token {
+        TokenValue::Tuple(tokens) => assert_eq!(tokens.len(), 3),
+        _ => panic!("expected tuple"),
+    }
+    let new_tuple: Tuple = token.unpack().unwrap();
+    assert_eq!(new_tuple.seqno, 5);
+    assert_eq!(new_tuple.header.balance, 1);
+    assert_eq!(new_tuple.header.total_supply, 2);
+}
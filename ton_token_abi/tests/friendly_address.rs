@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use ton_abi::TokenValue;
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::{AddressError, BuildTokenValue, FriendlyAddress};
+use ton_token_unpacker::UnpackToken;
+
+const RAW: &str = "0:a921453472366b7feeec15323a96b5dcf17197c88dc0d4578dfa52900b8a33cb";
+const BOUNCEABLE: &str = "EQCpIUU0cjZrf-7sFTI6lrXc8XGXyI3A1FeN-lKQC4ozy35r";
+const NON_BOUNCEABLE: &str = "UQCpIUU0cjZrf+7sFTI6lrXc8XGXyI3A1FeN+lKQC4ozyyOu";
+
+#[derive(PackAbi, UnpackAbi)]
+struct Wallet {
+    #[abi(address)]
+    owner: FriendlyAddress,
+    #[abi]
+    root: FriendlyAddress,
+}
+
+fn main() {
+    let bounceable = FriendlyAddress::from_str(BOUNCEABLE).unwrap();
+    assert!(bounceable.bounceable);
+    assert!(!bounceable.testnet);
+    assert_eq!(bounceable.to_string(), BOUNCEABLE);
+    assert_eq!(format!("{:#}", bounceable), RAW);
+
+    let non_bounceable = FriendlyAddress::from_str(NON_BOUNCEABLE).unwrap();
+    assert!(!non_bounceable.bounceable);
+    assert_eq!(non_bounceable.address, bounceable.address);
+    assert_eq!(
+        non_bounceable.to_string(),
+        NON_BOUNCEABLE.replace('+', "-").replace('/', "_")
+    );
+
+    let raw = FriendlyAddress::from_str(RAW).unwrap();
+    assert_eq!(raw, bounceable);
+
+    let testnet = FriendlyAddress {
+        testnet: true,
+        ..raw.clone()
+    };
+    let parsed = FriendlyAddress::from_str(&testnet.to_string()).unwrap();
+    assert!(parsed.testnet);
+    assert!(parsed.bounceable);
+
+    assert_eq!(
+        FriendlyAddress::from_str("EQCpIUU0cjZrf-7sFTI6lrXc8XGXyI3A1FeN-lKQC4ozy35s"),
+        Err(AddressError::InvalidChecksum)
+    );
+    assert_eq!(
+        FriendlyAddress::from_str("EQCpIUU0"),
+        Err(AddressError::InvalidLength(6))
+    );
+
+    let wallet = Wallet {
+        owner: bounceable,
+        root: testnet,
+    };
+    let value = wallet.token_value();
+    match &value {
+        TokenValue::Tuple(tokens) => assert!(matches!(tokens[1].value, TokenValue::Address(_))),
+        _ => panic!("expected tuple"),
+    }
+
+    let wallet: Wallet = value.unpack().unwrap();
+    assert_eq!(wallet.owner.to_string(), BOUNCEABLE);
+    assert_eq!(format!("{:#}", wallet.root), RAW);
+}
@@ -0,0 +1,79 @@
+#![allow(dead_code)]
+
+use arbitrary::{Arbitrary, Unstructured};
+use ton_abi::{ParamType, TokenValue};
+use ton_block::MsgAddressInt;
+use ton_token_abi::{KnownParamType, UnpackAbi};
+use ton_token_unpacker::fuzz::{valid_token_value, valid_tokens, FuzzTokenValue, FuzzTokens};
+use ton_token_unpacker::{ContractResult, KnownParamType, KnownParamTypePlain, UnpackToken};
+
+#[derive(UnpackAbi, KnownParamType, Debug)]
+enum Status {
+    Active,
+    Frozen,
+}
+
+#[derive(UnpackAbi, KnownParamType, Debug)]
+struct Wallet {
+    #[abi(address)]
+    owner: MsgAddressInt,
+    #[abi(uint128)]
+    balance: u128,
+    #[abi]
+    history: Vec<u32>,
+}
+
+#[derive(UnpackAbi, KnownParamType, Debug)]
+#[abi(plain)]
+struct Transfer {
+    #[abi]
+    wallet: Wallet,
+    #[abi(uint8)]
+    flags: u8,
+}
+
+fn data(seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    (0..4096)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 56) as u8
+        })
+        .collect()
+}
+
+fn main() {
+    let mut rejected = 0;
+    for seed in 0..256 {
+        let data = data(seed);
+
+        let mut u = Unstructured::new(&data);
+        let value = valid_token_value(&mut u, &Wallet::param_type()).unwrap();
+        let _: Wallet = value.unpack().unwrap();
+        let tokens = valid_tokens(&mut u, &Transfer::param_type()).unwrap();
+        let _: Transfer = tokens.unpack().unwrap();
+
+        let mut u = Unstructured::new(&data);
+        let value = FuzzTokenValue::<Wallet>::arbitrary(&mut u).unwrap();
+        let wallet: ContractResult<Wallet> = value.into_inner().unpack();
+        let tokens = FuzzTokens::<Transfer>::arbitrary(&mut u).unwrap();
+        let transfer: ContractResult<Transfer> = tokens.into_inner().unpack();
+        let status = FuzzTokenValue::<Status>::arbitrary(&mut u).unwrap();
+        let _: ContractResult<Status> = status.into_inner().unpack();
+
+        rejected += wallet.is_err() as usize + transfer.is_err() as usize;
+    }
+    assert!(rejected > 0);
+
+    let data = data(0);
+    let mut u = Unstructured::new(&data);
+    for param_type in [ParamType::VarUint(0), ParamType::VarInt(0)] {
+        match valid_token_value(&mut u, &param_type).unwrap() {
+            TokenValue::VarUint(0, number) => assert_eq!(number, 0u8.into()),
+            TokenValue::VarInt(0, number) => assert_eq!(number, 0u8.into()),
+            value => panic!("unexpected value {:?}", value),
+        }
+    }
+}
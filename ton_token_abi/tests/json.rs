@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use ton_block::MsgAddressInt;
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::UnpackToken;
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Clone, Debug, PartialEq)]
+#[abi(json)]
+struct Wallet {
+    #[abi(address)]
+    owner: MsgAddressInt,
+    #[abi(uint128)]
+    balance: u128,
+    #[abi]
+    frozen: bool,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Clone, Debug, PartialEq)]
+#[abi(plain, json)]
+struct Details {
+    #[abi]
+    wallet: Wallet,
+    #[abi(uint32)]
+    seqno: u32,
+}
+
+fn main() {
+    let wallet = Wallet {
+        owner: MsgAddressInt::from_str(
+            "0:18c99afffe13d3081370f77c10fc4d51bc54e52b8e181db6a0e8bb75456d91ff",
+        )
+        .unwrap(),
+        balance: 340282366920938463463374607431768211455,
+        frozen: false,
+    };
+
+    let json = serde_json::to_value(&wallet).unwrap();
+    assert_eq!(json["balance"], "340282366920938463463374607431768211455");
+    assert_eq!(
+        json["owner"],
+        "0:18c99afffe13d3081370f77c10fc4d51bc54e52b8e181db6a0e8bb75456d91ff"
+    );
+    assert_eq!(json["frozen"], false);
+    let parsed: Wallet = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, wallet);
+
+    let details = Details { wallet, seqno: 7 };
+    let json = serde_json::to_string(&details).unwrap();
+    let parsed: Details = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, details);
+
+    let invalid = serde_json::from_str::<Details>(r#"{"wallet": 1, "seqno": "7"}"#);
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,82 @@
+#![allow(dead_code)]
+
+use ton_abi::{Param, ParamType};
+use ton_block::MsgAddressInt;
+use ton_token_abi::KnownParamType;
+use ton_token_unpacker::{KnownParamType, KnownParamTypePlain};
+use ton_types::UInt256;
+
+#[derive(KnownParamType)]
+#[abi(repr = "uint16")]
+enum Status {
+    Active,
+}
+
+#[derive(KnownParamType)]
+struct Complex {
+    #[abi]
+    number: u8,
+    #[abi(name = "publicKey")]
+    public_key: Vec<u8>,
+}
+
+#[derive(KnownParamType)]
+#[abi(plain)]
+struct Header {
+    #[abi(uint256)]
+    pubkey: UInt256,
+    #[abi(address)]
+    owner: MsgAddressInt,
+}
+
+#[derive(KnownParamType)]
+#[abi(plain)]
+struct Details {
+    #[abi(flatten)]
+    header: Header,
+    #[abi]
+    status: Status,
+    #[abi]
+    complex: Complex,
+    #[abi]
+    seqnos: Vec<u32>,
+    _ignored: u32,
+}
+
+fn main() {
+    let complex = ParamType::Tuple(vec![
+        Param {
+            name: "number".to_string(),
+            kind: ParamType::Uint(8),
+        },
+        Param {
+            name: "publicKey".to_string(),
+            kind: ParamType::Bytes,
+        },
+    ]);
+    assert_eq!(Complex::param_type(), complex);
+
+    let params = vec![
+        Param {
+            name: "pubkey".to_string(),
+            kind: ParamType::Uint(256),
+        },
+        Param {
+            name: "owner".to_string(),
+            kind: ParamType::Address,
+        },
+        Param {
+            name: "status".to_string(),
+            kind: ParamType::Uint(16),
+        },
+        Param {
+            name: "complex".to_string(),
+            kind: complex,
+        },
+        Param {
+            name: "seqnos".to_string(),
+            kind: ParamType::Array(Box::new(ParamType::Uint(32))),
+        },
+    ];
+    assert_eq!(Details::param_type(), params);
+}
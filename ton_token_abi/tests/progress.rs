@@ -1,12 +1,39 @@
 #[test]
 fn tests() {
     let t = trybuild::TestCases::new();
+    t.pass("tests/address.rs");
+    t.pass("tests/allocations.rs");
+    t.pass("tests/amount.rs");
+    t.pass("tests/bitflags.rs");
+    t.pass("tests/boc.rs");
+    t.pass("tests/builder.rs");
+    t.pass("tests/cell_payload.rs");
+    t.pass("tests/cell_ref.rs");
+    t.pass("tests/checked.rs");
+    t.pass("tests/crypto.rs");
     t.pass("tests/enum.rs");
+    t.pass("tests/enum_other.rs");
+    t.pass("tests/enum_repr.rs");
+    t.pass("tests/facade.rs");
+    t.pass("tests/flatten.rs");
+    t.pass("tests/friendly_address.rs");
+    t.pass("tests/fuzz.rs");
+    t.pass("tests/json.rs");
     t.pass("tests/names.rs");
     t.pass("tests/pack_with.rs");
+    t.pass("tests/param_type.rs");
     t.pass("tests/plain_struct.rs");
+    t.pass("tests/roundtrip.rs");
+    t.pass("tests/serde.rs");
+    t.pass("tests/smart_pointers.rs");
     t.pass("tests/struct.rs");
+    t.pass("tests/token_values.rs");
     t.pass("tests/types.rs");
+    t.pass("tests/unpacker.rs");
     t.pass("tests/unpack_with.rs");
     t.pass("tests/vec.rs");
+    t.pass("tests/wide_uint.rs");
+    t.pass("tests/with.rs");
+
+    t.compile_fail("tests/ui/*.rs");
 }
@@ -0,0 +1,45 @@
+use proptest_derive::Arbitrary;
+use ton_block::MsgAddressInt;
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::roundtrip::{assert_roundtrip, assert_roundtrip_plain, strategy};
+use ton_token_unpacker::UnpackToken;
+use ton_types::UInt256;
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Arbitrary, Clone, Debug, PartialEq)]
+enum Status {
+    Active,
+    Frozen,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Arbitrary, Clone, Debug, PartialEq)]
+struct Wallet {
+    #[abi(address)]
+    #[proptest(strategy = "strategy::address()")]
+    owner: MsgAddressInt,
+    #[abi(uint128)]
+    balance: u128,
+    #[abi]
+    status: Status,
+    #[abi]
+    history: Vec<u32>,
+}
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Arbitrary, Clone, Debug, PartialEq)]
+#[abi(plain)]
+struct Transfer {
+    #[abi]
+    wallet: Wallet,
+    #[abi(uint256)]
+    #[proptest(strategy = "strategy::uint256()")]
+    public_key: UInt256,
+    #[abi(bool)]
+    bounce: bool,
+}
+
+fn main() {
+    assert_roundtrip::<u64>();
+    assert_roundtrip::<Status>();
+    assert_roundtrip::<Wallet>();
+    assert_roundtrip_plain::<Transfer>();
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use ton_abi::{Param, ParamType, TokenValue};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Status {
+    Active,
+    Frozen,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Meta {
+    name: String,
+    data: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Account {
+    owner: String,
+    balance: u128,
+    status: Status,
+    meta: Meta,
+    nonce: Option<u64>,
+    history: Vec<i32>,
+}
+
+fn schema() -> ParamType {
+    ParamType::Tuple(vec![
+        Param::new("owner", ParamType::Address),
+        Param::new("balance", ParamType::Uint(128)),
+        Param::new("status", ParamType::Uint(8)),
+        Param::new(
+            "meta",
+            ParamType::Tuple(vec![
+                Param::new("name", ParamType::String),
+                Param::new("data", ParamType::Bytes),
+            ]),
+        ),
+        Param::new("nonce", ParamType::Optional(Box::new(ParamType::Uint(64)))),
+        Param::new("history", ParamType::Array(Box::new(ParamType::Int(32)))),
+    ])
+}
+
+fn main() {
+    let account = Account {
+        owner: "0:18c99afffe13d3081370f77c10fc4d51bc54e52b8e181db6a0e8bb75456d91ff".to_owned(),
+        balance: 340282366920938463463374607431768211455,
+        status: Status::Frozen,
+        meta: Meta {
+            name: "main".to_owned(),
+            data: vec![1, 2, 3],
+        },
+        nonce: Some(42),
+        history: vec![-1, 0, 1],
+    };
+
+    let value = ton_token_packer::to_token_value(&account, &schema()).unwrap();
+    let tokens = match &value {
+        TokenValue::Tuple(tokens) => tokens,
+        _ => panic!("expected tuple"),
+    };
+    assert_eq!(tokens[1].name, "balance");
+    assert_eq!(
+        tokens[1].value,
+        TokenValue::Uint(ton_abi::Uint::new(account.balance, 128))
+    );
+    assert_eq!(tokens[2].value, TokenValue::Uint(ton_abi::Uint::new(1, 8)));
+    match &tokens[3].value {
+        TokenValue::Tuple(meta) => assert_eq!(meta[1].value, TokenValue::Bytes(vec![1, 2, 3])),
+        _ => panic!("expected tuple"),
+    }
+
+    let parsed: Account = ton_token_unpacker::from_token_value(value).unwrap();
+    assert_eq!(parsed, account);
+
+    let overflow = ton_token_packer::to_token_value(&300u32, &ParamType::Uint(8));
+    assert!(overflow.is_err());
+
+    let mismatch = ton_token_packer::to_token_value(&true, &ParamType::Uint(8));
+    assert!(mismatch.is_err());
+
+    for param_type in [
+        ParamType::VarUint(0),
+        ParamType::VarInt(0),
+        ParamType::Int(0),
+        ParamType::Uint(0),
+    ] {
+        let degenerate = ton_token_packer::to_token_value(&1u32, &param_type);
+        assert!(degenerate.is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use ton_abi::{ParamType, TokenValue, Uint};
+use ton_block::MsgAddressInt;
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::BuildTokenValue;
+use ton_token_unpacker::{KnownParamType, UnpackToken};
+use ton_types::UInt256;
+
+#[derive(PackAbi, UnpackAbi, KnownParamType, Debug, Clone, PartialEq)]
+struct Node {
+    #[abi(uint32)]
+    id: Box<u32>,
+    #[abi(uint256)]
+    hash: Arc<UInt256>,
+    #[abi(address)]
+    owner: Rc<MsgAddressInt>,
+    #[abi(uint64)]
+    weight: Cow<'static, u64>,
+    #[abi]
+    value: Arc<u128>,
+    #[abi]
+    children: Vec<Rc<u16>>,
+}
+
+fn main() {
+    let owner = Rc::new(
+        "0:a921453472366b7feeec15323a96b5dcf17197c88dc0d4578dfa52900b8a33cb"
+            .parse::<MsgAddressInt>()
+            .unwrap(),
+    );
+    let node = Node {
+        id: Box::new(1),
+        hash: Arc::new(UInt256::from([7; 32])),
+        owner: owner.clone(),
+        weight: Cow::Owned(10),
+        value: Arc::new(100),
+        children: vec![Rc::new(2), Rc::new(3)],
+    };
+
+    let value = node.clone().token_value();
+    match &value {
+        TokenValue::Tuple(tokens) => {
+            assert_eq!(tokens[0].value, TokenValue::Uint(Uint::new(1, 32)));
+            assert_eq!(tokens[3].value, TokenValue::Uint(Uint::new(10, 64)));
+            assert_eq!(tokens[4].value, TokenValue::Uint(Uint::new(100, 128)));
+        }
+        _ => panic!("expected tuple"),
+    }
+
+    let unpacked: Node = value.unpack().unwrap();
+    assert_eq!(unpacked, node);
+
+    let boxed: Box<u32> = TokenValue::Uint(Uint::new(5, 32)).unpack().unwrap();
+    assert_eq!(*boxed, 5);
+    assert_eq!(
+        Arc::new(5u32).token_value(),
+        TokenValue::Uint(Uint::new(5, 32))
+    );
+    assert_eq!(
+        <Box<u32> as KnownParamType>::param_type(),
+        ParamType::Uint(32)
+    );
+
+    match Node::param_type() {
+        ParamType::Tuple(params) => {
+            assert_eq!(params[0].kind, ParamType::Uint(32));
+            assert_eq!(params[4].kind, ParamType::Uint(128));
+            assert_eq!(
+                params[5].kind,
+                ParamType::Array(Box::new(ParamType::Uint(16)))
+            );
+        }
+        _ => panic!("expected tuple"),
+    }
+}
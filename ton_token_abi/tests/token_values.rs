@@ -0,0 +1,69 @@
+use ton_abi::{TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::{BuildTokenValue, BuildTokenValues};
+use ton_token_unpacker::{UnpackToken, UnpackTokenValues};
+
+#[derive(PackAbi, UnpackAbi, Debug, PartialEq)]
+#[abi(plain)]
+struct Inner {
+    #[abi(uint32)]
+    seqno: u32,
+}
+
+#[derive(PackAbi, UnpackAbi, Debug, PartialEq)]
+#[abi(plain)]
+struct Outer {
+    #[abi]
+    balance: u128,
+    #[abi(flatten)]
+    inner: Inner,
+    #[abi(name = "isActive")]
+    active: bool,
+}
+
+fn main() {
+    let values = (1u8, true, 7u32).token_values();
+    assert_eq!(
+        values,
+        vec![
+            TokenValue::Uint(Uint::new(1, 8)),
+            TokenValue::Bool(true),
+            TokenValue::Uint(Uint::new(7, 32)),
+        ]
+    );
+    let (a, b, c): (u8, bool, u32) = values.clone().unpack_values().unwrap();
+    assert_eq!((a, b, c), (1, true, 7));
+    assert!(UnpackTokenValues::<(u8, bool)>::unpack_values(values).is_err());
+
+    let values = vec![1u32, 2, 3].token_values();
+    let unpacked: Vec<u32> = values.clone().unpack_values().unwrap();
+    assert_eq!(unpacked, vec![1, 2, 3]);
+    let unpacked: [u32; 3] = values.clone().unpack_values().unwrap();
+    assert_eq!(unpacked, [1, 2, 3]);
+    assert!(UnpackTokenValues::<[u32; 2]>::unpack_values(values).is_err());
+
+    let values = [true, false].token_values();
+    assert_eq!(
+        values,
+        vec![TokenValue::Bool(true), TokenValue::Bool(false)]
+    );
+
+    let outer = Outer {
+        balance: 1337,
+        inner: Inner { seqno: 5 },
+        active: true,
+    };
+    let values = outer.token_values();
+    assert_eq!(values.len(), 3);
+    assert_eq!(values[1], TokenValue::Uint(Uint::new(5, 32)));
+
+    let unpacked: Outer = values.unpack_values().unwrap();
+    assert_eq!(
+        unpacked,
+        Outer {
+            balance: 1337,
+            inner: Inner { seqno: 5 },
+            active: true,
+        }
+    );
+}
@@ -0,0 +1,11 @@
+use ton_token_abi::PackAbi;
+
+#[derive(PackAbi)]
+struct Data {
+    #[abi(uint8)]
+    value: u32,
+    #[abi(uint256)]
+    creator: u64,
+}
+
+fn main() {}
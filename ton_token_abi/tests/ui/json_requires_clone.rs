@@ -0,0 +1,10 @@
+use ton_token_abi::PackAbi;
+
+#[derive(PackAbi)]
+#[abi(json)]
+struct Data {
+    #[abi(uint32)]
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,63 @@
+use ton_abi::{Token, TokenValue, Uint};
+use ton_token_abi::UnpackAbi;
+use ton_token_unpacker::{IntoUnpacker, UnpackToken, UnpackerError};
+
+#[derive(UnpackAbi, Debug, PartialEq)]
+#[abi(plain)]
+struct Tail {
+    #[abi(uint32)]
+    seqno: u32,
+    #[abi]
+    active: bool,
+}
+
+fn output() -> Vec<Token> {
+    vec![
+        Token::new("code", TokenValue::Uint(Uint::new(7, 8))),
+        Token::new("balance", TokenValue::Uint(Uint::new(1337, 128))),
+        Token::new("seqno", TokenValue::Uint(Uint::new(3, 32))),
+        Token::new("active", TokenValue::Bool(true)),
+    ]
+}
+
+fn main() {
+    let mut unpacker = output().into_unpacker();
+    assert_eq!(unpacker.remaining(), 4);
+    assert_eq!(unpacker.peek_name(), Some("code"));
+
+    let balance: u128 = unpacker.unpack_named("balance").unwrap();
+    assert_eq!(balance, 1337);
+    assert_eq!(unpacker.position(), 2);
+
+    let tail: Tail = unpacker.unpack_rest().unwrap();
+    assert_eq!(
+        tail,
+        Tail {
+            seqno: 3,
+            active: true
+        }
+    );
+    assert_eq!(unpacker.remaining(), 0);
+    unpacker.expect_end().unwrap();
+
+    let mut unpacker = output().into_unpacker();
+    unpacker.skip(2).unwrap();
+    match unpacker.unpack_next::<bool>() {
+        Err(UnpackerError::InvalidToken { position, name, .. }) => {
+            assert_eq!(position, 2);
+            assert_eq!(name, "seqno");
+        }
+        _ => panic!("expected invalid token"),
+    }
+    match unpacker.expect_end() {
+        Err(UnpackerError::UnexpectedToken { position, name }) => {
+            assert_eq!(position, 3);
+            assert_eq!(name, "active");
+        }
+        _ => panic!("expected unexpected token"),
+    }
+    assert!(unpacker.skip(2).is_err());
+
+    let mut unpacker = output().into_unpacker();
+    assert!(unpacker.unpack_named::<u32>("missing").is_err());
+}
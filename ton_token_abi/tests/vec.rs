@@ -1,30 +1,81 @@
-use ton_token_abi::{PackAbi, UnpackAbi};
-use ton_token_packer::BuildTokenValue;
-use ton_token_unpacker::UnpackToken;
+use ton_abi::{ParamType, TokenValue};
+use ton_token_abi::{KnownParamType, PackAbi, UnpackAbi};
+use ton_token_packer::{BuildTokenValue, StandaloneToken};
+use ton_token_unpacker::{KnownParamType, UnpackToken};
 
-#[derive(PackAbi, UnpackAbi)]
+#[derive(PackAbi, UnpackAbi, KnownParamType)]
 struct Data {
     #[abi(name = "vector")]
     vec: Vec<u32>,
     #[abi]
     complex: Complex,
+    #[abi]
+    complexes: Vec<Complex>,
+    #[abi]
+    nested: Vec<Vec<Complex>>,
+    #[abi]
+    bytes: Vec<u8>,
+    #[abi]
+    signed: Vec<i8>,
 }
 
-#[derive(PackAbi, UnpackAbi)]
+#[derive(Debug, PartialEq, PackAbi, UnpackAbi, KnownParamType)]
 struct Complex {
     #[abi]
     value: u32,
 }
 
+fn assert_standalone<T: StandaloneToken>() {}
+
 fn main() {
+    assert_standalone::<u8>();
+    assert_standalone::<i8>();
+    assert_standalone::<Vec<u8>>();
+    assert_standalone::<Vec<Vec<u32>>>();
+    assert_standalone::<ton_token_unpacker::CellPayload<Complex>>();
+    assert_standalone::<Complex>();
+    assert_standalone::<Vec<Complex>>();
+    assert_standalone::<ton_token_packer::TokenAmount<9>>();
+
     let data = Data {
         vec: vec![22, 44],
         complex: Complex { value: 2 },
+        complexes: vec![Complex { value: 3 }, Complex { value: 4 }],
+        nested: vec![vec![Complex { value: 5 }], vec![]],
+        bytes: vec![1, 2, 3],
+        signed: vec![-1, 1],
     };
 
     let tokens = data.token_value();
+    match &tokens {
+        TokenValue::Tuple(tokens) => {
+            assert!(matches!(tokens[2].value, TokenValue::Array(ref items) if items.len() == 2));
+            assert_eq!(tokens[4].value, TokenValue::Bytes(vec![1, 2, 3]));
+            assert!(matches!(tokens[5].value, TokenValue::Array(_)));
+        }
+        _ => panic!("expected tuple"),
+    }
+
     let new_data: Data = tokens.unpack().unwrap();
 
     assert_eq!(new_data.vec, vec![22, 44]);
     assert_eq!(new_data.complex.value, 2);
+    assert_eq!(
+        new_data.complexes,
+        vec![Complex { value: 3 }, Complex { value: 4 }]
+    );
+    assert_eq!(new_data.nested, vec![vec![Complex { value: 5 }], vec![]]);
+    assert_eq!(new_data.bytes, vec![1, 2, 3]);
+    assert_eq!(new_data.signed, vec![-1, 1]);
+
+    let complex = Box::new(Complex::param_type());
+    assert_eq!(
+        <Vec<Vec<Complex>>>::param_type(),
+        ParamType::Array(Box::new(ParamType::Array(complex)))
+    );
+    assert_eq!(<Vec<u8>>::param_type(), ParamType::Bytes);
+    assert_eq!(
+        <Vec<i8>>::param_type(),
+        ParamType::Array(Box::new(ParamType::Int(8)))
+    );
 }
@@ -0,0 +1,59 @@
+use num_bigint::BigUint;
+use ton_abi::{Token, TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::PackTokens;
+use ton_token_unpacker::{UnpackToken, UnpackerError};
+use ton_types::UInt256;
+
+#[derive(PackAbi, UnpackAbi, Clone, Debug, PartialEq)]
+#[abi(plain)]
+struct Data {
+    #[abi(uint160)]
+    address_hash: [u8; 20],
+    #[abi(uint160)]
+    address_bytes: Vec<u8>,
+    #[abi(uint256)]
+    hash: BigUint,
+    #[abi(uint256)]
+    hash_bytes: Vec<u8>,
+    #[abi(uint256)]
+    hash_array: [u8; 32],
+    #[abi(uint256)]
+    hash_uint: UInt256,
+    #[abi(biguint128)]
+    nonce: [u8; 16],
+    #[abi(biguint128)]
+    nonce_bytes: Vec<u8>,
+}
+
+fn main() {
+    let mut address_hash = [0; 20];
+    address_hash[19] = 1;
+    let mut nonce = [0; 16];
+    nonce[0] = 0xff;
+
+    let data = Data {
+        address_hash,
+        address_bytes: vec![0; 20],
+        hash: BigUint::from(256u32),
+        hash_bytes: (0..32).collect(),
+        hash_array: [0xaa; 32],
+        hash_uint: UInt256::from([0x55; 32]),
+        nonce,
+        nonce_bytes: vec![0; 16],
+    };
+
+    let tokens = data.clone().pack();
+    let parsed: Data = tokens.unpack().unwrap();
+    assert_eq!(parsed, data);
+
+    let overflow = vec![Token::new(
+        "address_hash",
+        TokenValue::Uint(Uint {
+            number: BigUint::from_bytes_be(&[1; 21]),
+            size: 160,
+        }),
+    )];
+    let parsed: Result<Data, _> = overflow.unpack();
+    assert!(matches!(parsed, Err(UnpackerError::InvalidAbi)));
+}
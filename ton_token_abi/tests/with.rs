@@ -0,0 +1,133 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use num_traits::ToPrimitive;
+use ton_abi::{Token, TokenValue, Uint};
+use ton_token_abi::{PackAbi, UnpackAbi};
+use ton_token_packer::{BuildTokenValue, PackerError, TokenAmount, TryBuildTokenValue};
+use ton_token_unpacker::{ContractResult, UnpackToken, UnpackerError};
+
+#[derive(PackAbi, UnpackAbi)]
+#[abi(checked)]
+struct Data {
+    #[abi(with = "seqno")]
+    seqno: u32,
+    #[abi(name = "createdAt", with = "ton_token::with::unix_time")]
+    created_at: std::time::SystemTime,
+    #[abi(with = "ton_token::with::nanotons")]
+    balance: TokenAmount<9>,
+    #[abi(name = "publicKey", with = "ton_token::with::hex")]
+    public_key: String,
+}
+
+#[derive(PackAbi)]
+struct Key {
+    #[abi(with = "ton_token::with::hex")]
+    value: String,
+}
+
+mod seqno {
+    use super::*;
+
+    pub fn pack(value: u32) -> Result<TokenValue, PackerError> {
+        Ok(TokenValue::Uint(Uint::new(value as u128, 32)))
+    }
+
+    pub fn unpack(value: &TokenValue) -> ContractResult<u32> {
+        match value {
+            TokenValue::Uint(Uint {
+                number: value,
+                size: 32,
+            }) => value.to_u32().ok_or(UnpackerError::InvalidAbi),
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+fn test() -> Data {
+    let seqno = Token::new("seqno", TokenValue::Uint(Uint::new(3, 32)));
+    let created_at = Token::new("createdAt", TokenValue::Uint(Uint::new(1625000000, 32)));
+    let balance = Token::new("balance", TokenValue::Uint(Uint::new(1500000000, 128)));
+    let public_key = Token::new("publicKey", TokenValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef]));
+    let tokens = vec![seqno, created_at, balance, public_key];
+
+    let tuple = Token::new("tuple", TokenValue::Tuple(tokens));
+    let parsed: Data = tuple.unpack().unwrap();
+
+    parsed
+}
+
+fn main() {
+    let data = test();
+    assert_eq!(data.seqno, 3);
+    assert_eq!(
+        data.created_at,
+        UNIX_EPOCH + Duration::from_secs(1625000000)
+    );
+    assert_eq!(data.balance.to_string(), "1.5");
+    assert_eq!(data.public_key, "deadbeef");
+
+    let token = Data {
+        seqno: 4,
+        created_at: UNIX_EPOCH + Duration::from_secs(1625000001),
+        balance: "12.000000345".parse().unwrap(),
+        public_key: "cafe".to_string(),
+    }
+    .try_token_value()
+    .unwrap();
+    let new_data: Data = token.unpack().unwrap();
+    assert_eq!(new_data.seqno, 4);
+    assert_eq!(
+        new_data.created_at,
+        UNIX_EPOCH + Duration::from_secs(1625000001)
+    );
+    assert_eq!(new_data.balance.to_string(), "12.000000345");
+    assert_eq!(new_data.public_key, "cafe");
+
+    for created_at in [
+        UNIX_EPOCH - Duration::from_secs(1),
+        UNIX_EPOCH + Duration::from_secs(u32::MAX as u64 + 1),
+    ] {
+        let data = Data {
+            seqno: 5,
+            created_at,
+            balance: TokenAmount::ZERO,
+            public_key: String::new(),
+        };
+        match data.try_token_value() {
+            Err(PackerError::IntegerOverflow { path, size }) => {
+                assert_eq!(path, "createdAt");
+                assert_eq!(size, 32);
+            }
+            _ => panic!("expected overflow"),
+        }
+    }
+
+    let data = Data {
+        seqno: 6,
+        created_at: UNIX_EPOCH,
+        balance: TokenAmount::ZERO,
+        public_key: "not hex".to_string(),
+    };
+    match data.try_token_value() {
+        Err(PackerError::InvalidValue { path, .. }) => assert_eq!(path, "publicKey"),
+        _ => panic!("expected invalid value"),
+    }
+
+    assert_eq!(
+        Key {
+            value: "beef".to_string()
+        }
+        .token_value(),
+        TokenValue::Tuple(vec![Token::new(
+            "value",
+            TokenValue::Bytes(vec![0xbe, 0xef])
+        )])
+    );
+    let result = std::panic::catch_unwind(|| {
+        Key {
+            value: "not hex".to_string(),
+        }
+        .token_value()
+    });
+    assert!(result.is_err());
+}
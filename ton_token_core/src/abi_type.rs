@@ -0,0 +1,73 @@
+//! Marker types for `#[abi(type)]` field attributes.
+//!
+//! The derives convert annotated fields through `IntoAbi` and `FromAbi`, which
+//! both require [`AbiCompatible`] for the marker of the type, so mismatched or
+//! lossy pairs are reported once at the field. Packing and unpacking accept
+//! exactly the same field types.
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub use self::names::{
+    address as Address, biguint128 as Biguint128, bool as Bool, cell as Cell, int8 as Int8,
+    uint128 as Uint128, uint16 as Uint16, uint160 as Uint160, uint256 as Uint256, uint32 as Uint32,
+    uint64 as Uint64, uint8 as Uint8,
+};
+
+/// Markers spelled like the ABI types, which is how diagnostics print them.
+#[allow(non_camel_case_types)]
+pub mod names {
+    pub struct int8;
+    pub struct uint8;
+    pub struct uint16;
+    pub struct uint32;
+    pub struct uint64;
+    pub struct uint128;
+    pub struct uint160;
+    pub struct uint256;
+    pub struct biguint128;
+    pub struct bool;
+    pub struct cell;
+    pub struct address;
+}
+
+#[diagnostic::on_unimplemented(
+    message = "field type `{Self}` cannot be encoded as `{A}`",
+    label = "incompatible with the `#[abi]` type"
+)]
+pub trait AbiCompatible<A> {}
+
+impl AbiCompatible<Int8> for i8 {}
+impl AbiCompatible<Uint8> for u8 {}
+impl AbiCompatible<Uint16> for u16 {}
+impl AbiCompatible<Uint32> for u32 {}
+impl AbiCompatible<Uint64> for u64 {}
+impl AbiCompatible<Uint128> for u128 {}
+impl AbiCompatible<Uint160> for num_bigint::BigUint {}
+impl AbiCompatible<Uint160> for [u8; 20] {}
+impl AbiCompatible<Uint160> for Vec<u8> {}
+impl AbiCompatible<Uint256> for num_bigint::BigUint {}
+impl AbiCompatible<Uint256> for ton_types::UInt256 {}
+impl AbiCompatible<Uint256> for [u8; 32] {}
+impl AbiCompatible<Uint256> for Vec<u8> {}
+impl AbiCompatible<Biguint128> for num_bigint::BigUint {}
+impl AbiCompatible<Biguint128> for [u8; 16] {}
+impl AbiCompatible<Biguint128> for Vec<u8> {}
+impl AbiCompatible<Bool> for bool {}
+impl AbiCompatible<Cell> for ton_types::Cell {}
+impl AbiCompatible<Address> for ton_block::MsgAddressInt {}
+impl AbiCompatible<Address> for ton_block::MsgAddrStd {}
+impl AbiCompatible<Address> for ton_block::MsgAddrExt {}
+impl AbiCompatible<Address> for ton_block::MsgAddress {}
+impl AbiCompatible<Address> for String {}
+impl<T> AbiCompatible<Address> for Option<T> where T: AbiCompatible<Address> {}
+impl<A, T> AbiCompatible<A> for Box<T> where T: AbiCompatible<A> {}
+impl<A, T> AbiCompatible<A> for Rc<T> where T: AbiCompatible<A> {}
+impl<A, T> AbiCompatible<A> for Arc<T> where T: AbiCompatible<A> {}
+impl<A, B> AbiCompatible<A> for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: AbiCompatible<A>,
+{
+}
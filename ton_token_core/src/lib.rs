@@ -0,0 +1,48 @@
+//! Definitions shared by `ton_token_packer` and `ton_token_unpacker`.
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use ton_abi::TokenValue;
+use ton_block::{MsgAddrExt, MsgAddrStd, MsgAddress, MsgAddressInt};
+use ton_types::{Cell, UInt256};
+
+pub mod abi_type;
+
+pub use abi_type::AbiCompatible;
+
+/// ABI version of cell contents, used unless a caller passes another one.
+pub const ABI_VERSION: u8 = 2;
+
+/// Marker for types which are represented by a single token value.
+///
+/// Vectors of any packable type are arrays (`Vec<u8>` is `bytes`), this
+/// marker only describes types and can be used as a bound in generic code.
+pub trait StandaloneToken {}
+impl StandaloneToken for MsgAddressInt {}
+impl StandaloneToken for MsgAddrStd {}
+impl StandaloneToken for MsgAddrExt {}
+impl StandaloneToken for MsgAddress {}
+impl StandaloneToken for Option<MsgAddressInt> {}
+impl StandaloneToken for Cell {}
+impl StandaloneToken for UInt256 {}
+impl StandaloneToken for i8 {}
+impl StandaloneToken for u8 {}
+impl StandaloneToken for u16 {}
+impl StandaloneToken for u32 {}
+impl StandaloneToken for u64 {}
+impl StandaloneToken for u128 {}
+impl StandaloneToken for bool {}
+impl StandaloneToken for String {}
+impl StandaloneToken for TokenValue {}
+impl<T: StandaloneToken> StandaloneToken for Vec<T> {}
+impl<T: StandaloneToken> StandaloneToken for Box<T> {}
+impl<T: StandaloneToken> StandaloneToken for Rc<T> {}
+impl<T: StandaloneToken> StandaloneToken for Arc<T> {}
+impl<B> StandaloneToken for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: StandaloneToken,
+{
+}
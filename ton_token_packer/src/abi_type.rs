@@ -0,0 +1,307 @@
+//! Conversions of `#[abi(type)]` fields, the markers are shared with
+//! `ton_token_unpacker` through `ton_token_core`.
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::checked::ROOT;
+use crate::PackerError;
+
+pub use ton_token_core::abi_type::{
+    AbiCompatible, Address, Biguint128, Bool, Cell, Int8, Uint128, Uint16, Uint160, Uint256,
+    Uint32, Uint64, Uint8,
+};
+
+impl AbiCompatible<Uint256> for crate::crypto::PublicKey {}
+impl AbiCompatible<Address> for crate::address::FriendlyAddress {}
+
+/// Conversion of fields with an `#[abi(type)]` attribute, `A` is the marker of the type.
+///
+/// Implementations are marked `do_not_recommend`, so a mismatch is reported
+/// with the message below instead of a list of supported types.
+#[diagnostic::on_unimplemented(
+    message = "field type `{Self}` cannot be encoded as `{A}`",
+    label = "incompatible with the `#[abi]` type"
+)]
+pub trait IntoAbi<A>: AbiCompatible<A> {
+    fn into_abi(self) -> ton_abi::TokenValue;
+}
+
+/// Fallible conversion of `#[abi(type)]` fields, used with `#[abi(checked)]`.
+///
+/// Every [`IntoAbi`] conversion is also a checked one. Conversions which can
+/// fail on user input, such as parsing a `String` address, only implement
+/// this trait.
+#[diagnostic::on_unimplemented(
+    message = "field type `{Self}` cannot be encoded as `{A}`",
+    label = "incompatible with the `#[abi]` type"
+)]
+pub trait TryIntoAbi<A>: AbiCompatible<A> {
+    fn try_into_abi(self) -> Result<ton_abi::TokenValue, PackerError>;
+}
+
+impl<T, A> TryIntoAbi<A> for T
+where
+    T: IntoAbi<A>,
+{
+    fn try_into_abi(self) -> Result<ton_abi::TokenValue, PackerError> {
+        crate::checked::checked(self.into_abi())
+    }
+}
+
+macro_rules! impl_into_abi_primitive {
+    ($($ty:ty => $marker:ty, $variant:ident, $number:ty, $size:literal;)*) => {
+        $(
+            #[diagnostic::do_not_recommend]
+            impl IntoAbi<$marker> for $ty {
+                fn into_abi(self) -> ton_abi::TokenValue {
+                    ton_abi::TokenValue::$variant(ton_abi::$variant {
+                        number: <$number>::from(self),
+                        size: $size,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_into_abi_uint {
+    ($($ty:ty => $marker:ty, $size:literal;)*) => {
+        $(
+            #[diagnostic::do_not_recommend]
+            impl IntoAbi<$marker> for $ty {
+                fn into_abi(self) -> ton_abi::TokenValue {
+                    ton_abi::TokenValue::Uint(ton_abi::Uint {
+                        number: IntoUint::into_uint(self),
+                        size: $size,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_into_abi_address {
+    ($($ty:ty),*) => {
+        $(
+            #[diagnostic::do_not_recommend]
+            impl IntoAbi<Address> for $ty {
+                fn into_abi(self) -> ton_abi::TokenValue {
+                    ton_abi::TokenValue::Address(IntoAddress::into_address(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_abi_primitive! {
+    i8 => Int8, Int, num_bigint::BigInt, 8;
+    u8 => Uint8, Uint, num_bigint::BigUint, 8;
+    u16 => Uint16, Uint, num_bigint::BigUint, 16;
+    u32 => Uint32, Uint, num_bigint::BigUint, 32;
+    u64 => Uint64, Uint, num_bigint::BigUint, 64;
+    u128 => Uint128, Uint, num_bigint::BigUint, 128;
+}
+
+impl_into_abi_uint! {
+    num_bigint::BigUint => Uint160, 160;
+    [u8; 20] => Uint160, 160;
+    Vec<u8> => Uint160, 160;
+    num_bigint::BigUint => Uint256, 256;
+    ton_types::UInt256 => Uint256, 256;
+    [u8; 32] => Uint256, 256;
+    Vec<u8> => Uint256, 256;
+    crate::crypto::PublicKey => Uint256, 256;
+    num_bigint::BigUint => Biguint128, 128;
+    [u8; 16] => Biguint128, 128;
+    Vec<u8> => Biguint128, 128;
+}
+
+impl_into_abi_address!(
+    ton_block::MsgAddress,
+    ton_block::MsgAddressInt,
+    ton_block::MsgAddrStd,
+    ton_block::MsgAddrExt,
+    crate::address::FriendlyAddress
+);
+
+#[diagnostic::do_not_recommend]
+impl<T> IntoAbi<Address> for Option<T>
+where
+    T: IntoAbi<Address>,
+{
+    fn into_abi(self) -> ton_abi::TokenValue {
+        match self {
+            Some(address) => address.into_abi(),
+            None => ton_abi::TokenValue::Address(ton_block::MsgAddress::AddrNone),
+        }
+    }
+}
+
+/// Addresses in raw or user-friendly form.
+#[diagnostic::do_not_recommend]
+impl TryIntoAbi<Address> for String {
+    fn try_into_abi(self) -> Result<ton_abi::TokenValue, PackerError> {
+        match crate::address::parse_address(&self) {
+            Ok(address) => Ok(ton_abi::TokenValue::Address(address.into_address())),
+            Err(e) => Err(PackerError::InvalidValue {
+                path: ROOT.to_owned(),
+                reason: format!("invalid address {:?}: {}", self, e),
+            }),
+        }
+    }
+}
+
+#[diagnostic::do_not_recommend]
+impl TryIntoAbi<Address> for Option<String> {
+    fn try_into_abi(self) -> Result<ton_abi::TokenValue, PackerError> {
+        match self {
+            Some(address) => address.try_into_abi(),
+            None => Ok(ton_abi::TokenValue::Address(
+                ton_block::MsgAddress::AddrNone,
+            )),
+        }
+    }
+}
+
+#[diagnostic::do_not_recommend]
+impl IntoAbi<Bool> for bool {
+    fn into_abi(self) -> ton_abi::TokenValue {
+        ton_abi::TokenValue::Bool(self)
+    }
+}
+
+#[diagnostic::do_not_recommend]
+impl IntoAbi<Cell> for ton_types::Cell {
+    fn into_abi(self) -> ton_abi::TokenValue {
+        ton_abi::TokenValue::Cell(self)
+    }
+}
+
+/// Smart pointers around `#[abi(type)]` fields, unwrapped before packing.
+pub trait IntoInner {
+    type Inner;
+
+    fn into_inner(self) -> Self::Inner;
+}
+
+impl<T> IntoInner for Box<T> {
+    type Inner = T;
+
+    fn into_inner(self) -> T {
+        *self
+    }
+}
+
+impl<T> IntoInner for Rc<T>
+where
+    T: Clone,
+{
+    type Inner = T;
+
+    fn into_inner(self) -> T {
+        Rc::try_unwrap(self).unwrap_or_else(|value| (*value).clone())
+    }
+}
+
+impl<T> IntoInner for Arc<T>
+where
+    T: Clone,
+{
+    type Inner = T;
+
+    fn into_inner(self) -> T {
+        Arc::try_unwrap(self).unwrap_or_else(|value| (*value).clone())
+    }
+}
+
+impl<B> IntoInner for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+{
+    type Inner = B::Owned;
+
+    fn into_inner(self) -> B::Owned {
+        self.into_owned()
+    }
+}
+
+/// Conversion of wide unsigned integer fields (`uint160`, `uint256`, `biguint128`).
+pub trait IntoUint {
+    fn into_uint(self) -> num_bigint::BigUint;
+}
+
+impl IntoUint for num_bigint::BigUint {
+    fn into_uint(self) -> num_bigint::BigUint {
+        self
+    }
+}
+
+impl IntoUint for ton_types::UInt256 {
+    fn into_uint(self) -> num_bigint::BigUint {
+        num_bigint::BigUint::from_bytes_be(self.as_slice())
+    }
+}
+
+impl<const N: usize> IntoUint for [u8; N] {
+    fn into_uint(self) -> num_bigint::BigUint {
+        num_bigint::BigUint::from_bytes_be(&self)
+    }
+}
+
+impl IntoUint for Vec<u8> {
+    fn into_uint(self) -> num_bigint::BigUint {
+        num_bigint::BigUint::from_bytes_be(&self)
+    }
+}
+
+/// Conversion of `address` fields. `None` is packed as `addr_none`.
+pub trait IntoAddress {
+    fn into_address(self) -> ton_block::MsgAddress;
+}
+
+impl IntoAddress for ton_block::MsgAddress {
+    fn into_address(self) -> ton_block::MsgAddress {
+        self
+    }
+}
+
+impl IntoAddress for ton_block::MsgAddressInt {
+    fn into_address(self) -> ton_block::MsgAddress {
+        match self {
+            ton_block::MsgAddressInt::AddrStd(addr) => ton_block::MsgAddress::AddrStd(addr),
+            ton_block::MsgAddressInt::AddrVar(addr) => ton_block::MsgAddress::AddrVar(addr),
+        }
+    }
+}
+
+impl IntoAddress for ton_block::MsgAddrStd {
+    fn into_address(self) -> ton_block::MsgAddress {
+        ton_block::MsgAddress::AddrStd(self)
+    }
+}
+
+impl IntoAddress for ton_block::MsgAddrExt {
+    fn into_address(self) -> ton_block::MsgAddress {
+        ton_block::MsgAddress::AddrExt(self)
+    }
+}
+
+impl IntoAddress for crate::address::FriendlyAddress {
+    fn into_address(self) -> ton_block::MsgAddress {
+        ton_block::MsgAddress::AddrStd(self.address)
+    }
+}
+
+impl<T> IntoAddress for Option<T>
+where
+    T: IntoAddress,
+{
+    fn into_address(self) -> ton_block::MsgAddress {
+        match self {
+            Some(address) => address.into_address(),
+            None => ton_block::MsgAddress::AddrNone,
+        }
+    }
+}
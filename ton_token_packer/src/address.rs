@@ -0,0 +1,163 @@
+//! Address strings in raw (`0:3333...`) and user-friendly (`EQAz...`) forms.
+//!
+//! A user-friendly address is a base64 encoded tag, workchain, 32 bytes of
+//! the address and a CRC-16 checksum, either in standard or url-safe alphabet.
+
+use std::str::FromStr;
+
+use ton_block::{MsgAddrStd, MsgAddressInt};
+use ton_types::SliceData;
+
+/// Length of a decoded user-friendly address: tag, workchain, address and checksum.
+const FRIENDLY_LEN: usize = 36;
+
+const BOUNCEABLE_TAG: u8 = 0x11;
+const NON_BOUNCEABLE_TAG: u8 = 0x51;
+const TESTNET_FLAG: u8 = 0x80;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum AddressError {
+    #[error("Invalid raw address")]
+    InvalidRaw,
+    #[error("Invalid base64 encoding")]
+    InvalidBase64,
+    #[error("Invalid user-friendly address length (expected 36 bytes, found {0})")]
+    InvalidLength(usize),
+    #[error("Invalid user-friendly address tag {0:#04x}")]
+    InvalidTag(u8),
+    #[error("Invalid user-friendly address checksum")]
+    InvalidChecksum,
+}
+
+/// Parses an address in either raw or user-friendly form.
+pub fn parse_address(s: &str) -> Result<MsgAddressInt, AddressError> {
+    if s.contains(':') {
+        MsgAddressInt::from_str(s).map_err(|_| AddressError::InvalidRaw)
+    } else {
+        decode_friendly(s).map(|(_, address)| address)
+    }
+}
+
+/// Decodes a user-friendly address, returning its tag along with the address.
+fn decode_friendly(s: &str) -> Result<(u8, MsgAddressInt), AddressError> {
+    let config = if s.contains(['-', '_']) {
+        base64::URL_SAFE
+    } else {
+        base64::STANDARD
+    };
+    let data = base64::decode_config(s, config).map_err(|_| AddressError::InvalidBase64)?;
+    if data.len() != FRIENDLY_LEN {
+        return Err(AddressError::InvalidLength(data.len()));
+    }
+
+    let tag = data[0];
+    if !matches!(tag & !TESTNET_FLAG, BOUNCEABLE_TAG | NON_BOUNCEABLE_TAG) {
+        return Err(AddressError::InvalidTag(tag));
+    }
+    let checksum = u16::from_be_bytes([data[34], data[35]]);
+    if crc16(&data[..34]) != checksum {
+        return Err(AddressError::InvalidChecksum);
+    }
+
+    let address = MsgAddressInt::with_standart(
+        None,
+        data[1] as i8,
+        SliceData::from_raw(data[2..34].to_vec(), 256),
+    )
+    .map_err(|_| AddressError::InvalidRaw)?;
+    Ok((tag, address))
+}
+
+/// CRC-16/XMODEM, used as a checksum of user-friendly addresses.
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &byte| {
+        (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Standard address along with the flags of its user-friendly form.
+///
+/// Packed as `address`. Formatted in url-safe user-friendly form, or in raw
+/// form with `{:#}`. Parsed from either form, raw addresses are bounceable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FriendlyAddress {
+    pub bounceable: bool,
+    pub testnet: bool,
+    pub address: MsgAddrStd,
+}
+
+impl FriendlyAddress {
+    pub fn new(address: MsgAddrStd) -> Self {
+        Self {
+            bounceable: true,
+            testnet: false,
+            address,
+        }
+    }
+
+    fn encode(&self) -> [u8; FRIENDLY_LEN] {
+        let mut data = [0; FRIENDLY_LEN];
+        data[0] = match self.bounceable {
+            true => BOUNCEABLE_TAG,
+            false => NON_BOUNCEABLE_TAG,
+        };
+        if self.testnet {
+            data[0] |= TESTNET_FLAG;
+        }
+        data[1] = self.address.workchain_id as u8;
+        data[2..34].copy_from_slice(&self.address.address.get_bytestring(0));
+        let checksum = crc16(&data[..34]);
+        data[34..].copy_from_slice(&checksum.to_be_bytes());
+        data
+    }
+}
+
+impl std::fmt::Display for FriendlyAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", MsgAddressInt::AddrStd(self.address.clone()))
+        } else {
+            f.write_str(&base64::encode_config(self.encode(), base64::URL_SAFE))
+        }
+    }
+}
+
+impl FromStr for FriendlyAddress {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tag, address) = if s.contains(':') {
+            let address = MsgAddressInt::from_str(s).map_err(|_| AddressError::InvalidRaw)?;
+            (BOUNCEABLE_TAG, address)
+        } else {
+            decode_friendly(s)?
+        };
+
+        match address {
+            MsgAddressInt::AddrStd(address) => Ok(Self {
+                bounceable: tag & !TESTNET_FLAG == BOUNCEABLE_TAG,
+                testnet: tag & TESTNET_FLAG != 0,
+                address,
+            }),
+            MsgAddressInt::AddrVar(_) => Err(AddressError::InvalidRaw),
+        }
+    }
+}
+
+impl From<MsgAddrStd> for FriendlyAddress {
+    fn from(address: MsgAddrStd) -> Self {
+        Self::new(address)
+    }
+}
+
+impl From<FriendlyAddress> for MsgAddressInt {
+    fn from(address: FriendlyAddress) -> Self {
+        MsgAddressInt::AddrStd(address.address)
+    }
+}
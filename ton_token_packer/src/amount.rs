@@ -0,0 +1,139 @@
+//! Token amounts with a fixed number of decimals, packed as `uint128` units.
+
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use ton_abi::TokenValue;
+
+use crate::{BuildTokenValue, StandaloneToken};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum AmountError {
+    #[error("Invalid decimal amount")]
+    InvalidFormat,
+    #[error("Too many fractional digits (at most {0} are allowed)")]
+    TooManyDecimals(u8),
+    #[error("Amount doesn't fit into uint128")]
+    Overflow,
+}
+
+/// Amount of `10^-DECIMALS` units, e.g. `TokenAmount<9>` for nanotons.
+///
+/// Formats and parses as an exact decimal (`"12.345"`), so no precision is
+/// lost in either direction.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount<const DECIMALS: u8>(u128);
+
+impl<const DECIMALS: u8> TokenAmount<DECIMALS> {
+    pub const ZERO: Self = Self(0);
+
+    /// Number of units in one token. Fails to compile for more than 38 decimals.
+    pub const SCALE: u128 = 10u128.pow(DECIMALS as u32);
+
+    pub const fn from_units(units: u128) -> Self {
+        Self(units)
+    }
+
+    pub const fn units(self) -> u128 {
+        self.0
+    }
+
+    /// Amount of whole tokens.
+    pub fn from_tokens(tokens: u128) -> Option<Self> {
+        tokens.checked_mul(Self::SCALE).map(Self)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    pub fn checked_mul(self, factor: u128) -> Option<Self> {
+        self.0.checked_mul(factor).map(Self)
+    }
+
+    /// Divides the amount, rounding towards zero.
+    pub fn checked_div(self, divisor: u128) -> Option<Self> {
+        self.0.checked_div(divisor).map(Self)
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+}
+
+impl<const DECIMALS: u8> std::fmt::Display for TokenAmount<DECIMALS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let int = self.0 / Self::SCALE;
+        let frac = self.0 % Self::SCALE;
+
+        if frac == 0 {
+            write!(f, "{}", int)
+        } else {
+            let frac = format!("{:0>width$}", frac, width = DECIMALS as usize);
+            write!(f, "{}.{}", int, frac.trim_end_matches('0'))
+        }
+    }
+}
+
+impl<const DECIMALS: u8> FromStr for TokenAmount<DECIMALS> {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (int, frac) = match s.find('.') {
+            Some(pos) if pos + 1 < s.len() => (&s[..pos], &s[pos + 1..]),
+            Some(_) => return Err(AmountError::InvalidFormat),
+            None => (s, ""),
+        };
+        if int.is_empty() || !is_digits(int) || !is_digits(frac) {
+            return Err(AmountError::InvalidFormat);
+        }
+        if frac.len() > DECIMALS as usize {
+            return Err(AmountError::TooManyDecimals(DECIMALS));
+        }
+
+        let int = int.parse::<u128>().map_err(|_| AmountError::Overflow)?;
+        let frac = match frac.is_empty() {
+            true => 0,
+            false => {
+                frac.parse::<u128>().map_err(|_| AmountError::Overflow)?
+                    * 10u128.pow((DECIMALS as usize - frac.len()) as u32)
+            }
+        };
+
+        int.checked_mul(Self::SCALE)
+            .and_then(|units| units.checked_add(frac))
+            .map(Self)
+            .ok_or(AmountError::Overflow)
+    }
+}
+
+fn is_digits(value: &str) -> bool {
+    value.bytes().all(|c| c.is_ascii_digit())
+}
+
+impl<const DECIMALS: u8> From<TokenAmount<DECIMALS>> for u128 {
+    fn from(amount: TokenAmount<DECIMALS>) -> Self {
+        amount.0
+    }
+}
+
+impl<const DECIMALS: u8> From<u128> for TokenAmount<DECIMALS> {
+    fn from(units: u128) -> Self {
+        Self(units)
+    }
+}
+
+impl<const DECIMALS: u8> BuildTokenValue for TokenAmount<DECIMALS> {
+    fn token_value(self) -> TokenValue {
+        TokenValue::Uint(ton_abi::Uint {
+            number: BigUint::from(self.0),
+            size: 128,
+        })
+    }
+}
+
+impl<const DECIMALS: u8> StandaloneToken for TokenAmount<DECIMALS> {}
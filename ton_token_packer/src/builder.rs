@@ -0,0 +1,156 @@
+use num_bigint::{BigInt, BigUint};
+use ton_abi::{Function, Param, Token, TokenValue};
+use ton_block::MsgAddressInt;
+use ton_types::Cell;
+
+use crate::{BuildTokenValue, PackTokens};
+
+/// Fluent builder for one-off function inputs.
+///
+/// A builder created with [`TokensBuilder::for_function`] checks every pushed
+/// token against the function inputs, the first mismatch is reported by
+/// [`TokensBuilder::try_build`].
+#[derive(Debug, Clone, Default)]
+pub struct TokensBuilder<'a> {
+    tokens: Vec<Token>,
+    params: Option<&'a [Param]>,
+    error: Option<BuilderError>,
+}
+
+impl<'a> TokensBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn for_function(function: &'a Function) -> Self {
+        Self::for_params(&function.inputs)
+    }
+
+    pub fn for_params(params: &'a [Param]) -> Self {
+        Self {
+            tokens: Vec::with_capacity(params.len()),
+            params: Some(params),
+            error: None,
+        }
+    }
+
+    pub fn uint(self, size: usize, name: &str, value: u128) -> Self {
+        self.value(
+            name,
+            TokenValue::Uint(ton_abi::Uint {
+                number: BigUint::from(value),
+                size,
+            }),
+        )
+    }
+
+    pub fn int(self, size: usize, name: &str, value: i128) -> Self {
+        self.value(
+            name,
+            TokenValue::Int(ton_abi::Int {
+                number: BigInt::from(value),
+                size,
+            }),
+        )
+    }
+
+    pub fn bool(self, name: &str, value: bool) -> Self {
+        self.value(name, value)
+    }
+
+    pub fn address(self, name: &str, value: MsgAddressInt) -> Self {
+        self.value(name, value)
+    }
+
+    pub fn cell(self, name: &str, value: Cell) -> Self {
+        self.value(name, value)
+    }
+
+    pub fn bytes(self, name: &str, value: Vec<u8>) -> Self {
+        self.value(name, value)
+    }
+
+    pub fn string(self, name: &str, value: &str) -> Self {
+        self.value(name, TokenValue::String(value.to_string()))
+    }
+
+    pub fn value<T>(self, name: &str, value: T) -> Self
+    where
+        T: BuildTokenValue,
+    {
+        self.token(Token::new(name, value.token_value()))
+    }
+
+    /// Appends all tokens of a plain struct.
+    pub fn tokens<T>(self, value: T) -> Self
+    where
+        T: PackTokens,
+    {
+        value.pack().into_iter().fold(self, Self::token)
+    }
+
+    pub fn token(mut self, token: Token) -> Self {
+        if self.error.is_none() {
+            self.error = self.check(&token).err();
+        }
+        self.tokens.push(token);
+        self
+    }
+
+    /// Returns tokens, ignoring validation errors.
+    pub fn build(self) -> Vec<Token> {
+        self.tokens
+    }
+
+    pub fn try_build(self) -> Result<Vec<Token>, BuilderError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if let Some(params) = self.params {
+            if let Some(param) = params.get(self.tokens.len()) {
+                return Err(BuilderError::MissingToken {
+                    name: param.name.clone(),
+                });
+            }
+        }
+        Ok(self.tokens)
+    }
+
+    fn check(&self, token: &Token) -> Result<(), BuilderError> {
+        let params = match self.params {
+            Some(params) => params,
+            None => return Ok(()),
+        };
+
+        let param = params
+            .get(self.tokens.len())
+            .ok_or_else(|| BuilderError::UnexpectedToken {
+                name: token.name.clone(),
+            })?;
+        if param.name != token.name {
+            return Err(BuilderError::InvalidName {
+                expected: param.name.clone(),
+                found: token.name.clone(),
+            });
+        }
+        if !token.value.type_check(&param.kind) {
+            return Err(BuilderError::InvalidType {
+                name: token.name.clone(),
+                expected: param.kind.type_signature(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum BuilderError {
+    #[error("Invalid name (expected {expected:?}, found {found:?})")]
+    InvalidName { expected: String, found: String },
+    #[error("Invalid type of {name:?} (expected {expected})")]
+    InvalidType { name: String, expected: String },
+    #[error("Unexpected token {name:?}")]
+    UnexpectedToken { name: String },
+    #[error("Missing token {name:?}")]
+    MissingToken { name: String },
+}
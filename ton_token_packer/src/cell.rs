@@ -0,0 +1,95 @@
+//! Packing of values stored in a separate cell, see `#[abi(cell_of = "...")]`,
+//! and of plain structs into cells and bags of cells.
+
+use ton_abi::{Token, TokenValue};
+use ton_types::Cell;
+
+use crate::checked::check;
+use crate::store::{BuilderData, StoreBuilder};
+use crate::{BuildTokenValue, PackerError, TryBuildTokenValue, TryPackTokens};
+
+pub use ton_token_core::ABI_VERSION;
+
+/// Encodes the value into a new cell. Tuples are stored component-wise.
+pub fn build_cell<T>(value: T, abi_version: u8) -> Result<Cell, PackerError>
+where
+    T: TryBuildTokenValue,
+{
+    encode_checked(&value.try_token_value()?, abi_version)
+}
+
+/// Builds the token of a `#[abi(cell_of = "...")]` field.
+///
+/// A value which can't be encoded is kept as a `ref`, so `TryBuildTokenValue`
+/// reports the offending field and `ton_abi` rejects the token.
+#[doc(hidden)]
+pub fn cell_of<T>(value: T) -> TokenValue
+where
+    T: BuildTokenValue,
+{
+    let value = value.token_value();
+    match encode_value(&value, ABI_VERSION) {
+        Ok(cell) => TokenValue::Cell(cell),
+        Err(_) => TokenValue::Ref(Box::new(value)),
+    }
+}
+
+/// Builds the token of a `#[abi(cell_of = "...")]` field in the `checked` derive.
+#[doc(hidden)]
+pub fn try_cell_of<T>(value: T) -> Result<TokenValue, PackerError>
+where
+    T: TryBuildTokenValue,
+{
+    let value = value.try_token_value()?;
+    encode_checked(&value, ABI_VERSION).map(TokenValue::Cell)
+}
+
+/// Encodes the tokens of a plain struct into a new cell.
+pub fn to_cell<T>(value: &T, abi_version: u8) -> Result<Cell, PackerError>
+where
+    T: Clone + TryPackTokens,
+{
+    let tokens = value.clone().try_pack()?;
+    pack_tokens(&tokens, abi_version)
+}
+
+/// Encodes the tokens of a plain struct into a base64 encoded bag of cells.
+pub fn to_boc_base64<T>(value: &T, abi_version: u8) -> Result<String, PackerError>
+where
+    T: Clone + TryPackTokens,
+{
+    let cell = to_cell(value, abi_version)?;
+    let boc =
+        ton_types::serialize_toc(&cell).map_err(|e| PackerError::InvalidCell(e.to_string()))?;
+    Ok(base64::encode(boc))
+}
+
+/// Writes the value into a new cell without building tokens, see [`StoreBuilder`].
+pub fn store_cell<T>(value: &T) -> Result<Cell, PackerError>
+where
+    T: StoreBuilder,
+{
+    let mut builder = BuilderData::new();
+    value.store_into(&mut builder)?;
+    builder
+        .into_cell()
+        .map_err(|e| PackerError::InvalidCell(e.to_string()))
+}
+
+fn encode_value(value: &TokenValue, abi_version: u8) -> Result<Cell, PackerError> {
+    check(value)?;
+    encode_checked(value, abi_version)
+}
+
+fn encode_checked(value: &TokenValue, abi_version: u8) -> Result<Cell, PackerError> {
+    match value {
+        TokenValue::Tuple(tokens) => pack_tokens(tokens, abi_version),
+        value => pack_tokens(&[Token::new("value", value.clone())], abi_version),
+    }
+}
+
+fn pack_tokens(tokens: &[Token], abi_version: u8) -> Result<Cell, PackerError> {
+    TokenValue::pack_values_into_chain(tokens, Vec::new(), abi_version)
+        .and_then(|builder| builder.into_cell())
+        .map_err(|e| PackerError::InvalidCell(e.to_string()))
+}
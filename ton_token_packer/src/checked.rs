@@ -0,0 +1,242 @@
+//! Fallible packing which validates that every integer fits its declared width.
+//!
+//! `BuildTokenValue` implementations can't fail, so an out-of-range value
+//! would only be rejected by `ton_abi` later, without any hint which field it
+//! came from. These traits check the built value and report the path of the
+//! offending token, e.g. `wallet.balance` or `items[2]`.
+//!
+//! Types deriving `PackAbi` with `#[abi(checked)]` implement these traits
+//! directly instead of `BuildTokenValue`/`PackTokens`, which allows fields
+//! whose conversion can fail on user input.
+
+use ton_abi::{Token, TokenValue};
+
+use crate::width::{check_int, check_uint, var_bits, Overflow};
+use crate::{BuildTokenValue, PackTokens};
+
+pub trait TryBuildTokenValue {
+    fn try_token_value(self) -> Result<TokenValue, PackerError>;
+}
+
+impl<T> TryBuildTokenValue for T
+where
+    T: BuildTokenValue,
+{
+    fn try_token_value(self) -> Result<TokenValue, PackerError> {
+        let value = self.token_value();
+        check(&value)?;
+        Ok(value)
+    }
+}
+
+pub trait TryPackTokens {
+    fn try_pack(self) -> Result<Vec<Token>, PackerError>;
+}
+
+impl<T> TryPackTokens for T
+where
+    T: PackTokens,
+{
+    fn try_pack(self) -> Result<Vec<Token>, PackerError> {
+        let tokens = self.pack();
+        check_tokens(&tokens, &mut Path::default())?;
+        Ok(tokens)
+    }
+}
+
+/// Path of a value which isn't nested in a tuple, array or map.
+pub(crate) const ROOT: &str = "<root>";
+
+/// Checks the payload of an `#[abi(other)]` enum variant in the `checked` mode.
+///
+/// A payload equal to the tag of a declared variant would be unpacked as that
+/// variant, so it is rejected.
+#[doc(hidden)]
+pub fn try_other_variant(
+    value: TokenValue,
+    tags: &[TokenValue],
+) -> Result<TokenValue, PackerError> {
+    if tags.contains(&value) {
+        return Err(PackerError::InvalidValue {
+            path: ROOT.to_owned(),
+            reason: "payload of the `other` variant is the tag of a declared variant".to_owned(),
+        });
+    }
+    checked(value)
+}
+
+/// Builds the payload of an `#[abi(other)]` enum variant.
+///
+/// # Panics
+///
+/// Panics if the payload is the tag of a declared variant, use the `checked`
+/// mode to get an error instead.
+#[doc(hidden)]
+pub fn other_variant(value: TokenValue, tags: &[TokenValue]) -> TokenValue {
+    assert!(
+        !tags.contains(&value),
+        "payload of the `other` variant is the tag of a declared variant"
+    );
+    value
+}
+
+/// Unwraps the result of a `with` adapter in the infallible derive.
+///
+/// # Panics
+///
+/// Panics with the name of the field if the adapter failed, use the `checked`
+/// mode to get an error instead.
+#[doc(hidden)]
+#[track_caller]
+pub fn expect_field(result: Result<TokenValue, PackerError>, name: &str) -> TokenValue {
+    match result {
+        Ok(value) => value,
+        Err(e) => panic!("{}", e.in_field(name)),
+    }
+}
+
+/// Checks a value built by the `checked` derive, see [`TryBuildTokenValue`].
+#[doc(hidden)]
+pub fn checked(value: TokenValue) -> Result<TokenValue, PackerError> {
+    check(&value)?;
+    Ok(value)
+}
+
+/// Checks a token built by a `pack_with` function in the `checked` derive.
+#[doc(hidden)]
+pub fn check_token(token: Token) -> Result<Token, PackerError> {
+    check_tokens(std::slice::from_ref(&token), &mut Path::default())?;
+    Ok(token)
+}
+
+/// Checks an already built value, see [`TryBuildTokenValue`].
+pub(crate) fn check(value: &TokenValue) -> Result<(), PackerError> {
+    check_value(value, &mut Path::default())
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum PackerError {
+    #[error("Value of `{path}` doesn't fit into {size} bits")]
+    IntegerOverflow { path: String, size: usize },
+    #[error("Invalid length of `{path}` (expected {expected}, found {found})")]
+    InvalidLength {
+        path: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Invalid value of `{path}`: {reason}")]
+    InvalidValue { path: String, reason: String },
+    #[error("Invalid cell: {0}")]
+    InvalidCell(String),
+}
+
+impl PackerError {
+    /// Prefixes the path of the error with the name of the field it came from.
+    #[doc(hidden)]
+    pub fn in_field(self, name: &str) -> Self {
+        let prefix = |path: String| {
+            if path == ROOT {
+                name.to_owned()
+            } else if path.starts_with('[') {
+                format!("{}{}", name, path)
+            } else {
+                format!("{}.{}", name, path)
+            }
+        };
+        match self {
+            Self::IntegerOverflow { path, size } => Self::IntegerOverflow {
+                path: prefix(path),
+                size,
+            },
+            Self::InvalidLength {
+                path,
+                expected,
+                found,
+            } => Self::InvalidLength {
+                path: prefix(path),
+                expected,
+                found,
+            },
+            Self::InvalidValue { path, reason } => Self::InvalidValue {
+                path: prefix(path),
+                reason,
+            },
+            Self::InvalidCell(reason) => Self::InvalidCell(reason),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Path(Vec<String>);
+
+impl Path {
+    fn with<F>(&mut self, segment: String, f: F) -> Result<(), PackerError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), PackerError>,
+    {
+        self.0.push(segment);
+        let result = f(self);
+        self.0.pop();
+        result
+    }
+}
+
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str(ROOT);
+        }
+        for (i, segment) in self.0.iter().enumerate() {
+            if i > 0 && !segment.starts_with('[') {
+                f.write_str(".")?;
+            }
+            f.write_str(segment)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_tokens(tokens: &[Token], path: &mut Path) -> Result<(), PackerError> {
+    tokens
+        .iter()
+        .try_for_each(|token| path.with(token.name.clone(), |path| check_value(&token.value, path)))
+}
+
+fn check_values(values: &[TokenValue], path: &mut Path) -> Result<(), PackerError> {
+    values
+        .iter()
+        .enumerate()
+        .try_for_each(|(i, value)| path.with(format!("[{}]", i), |path| check_value(value, path)))
+}
+
+fn check_value(value: &TokenValue, path: &mut Path) -> Result<(), PackerError> {
+    match value {
+        TokenValue::Uint(int) => check_uint(&int.number, int.size).map_err(overflow(path)),
+        TokenValue::Int(int) => check_int(&int.number, int.size).map_err(overflow(path)),
+        TokenValue::VarUint(size, number) => var_bits(*size)
+            .and_then(|bits| check_uint(number, bits))
+            .map_err(overflow(path)),
+        TokenValue::VarInt(size, number) => var_bits(*size)
+            .and_then(|bits| check_int(number, bits))
+            .map_err(overflow(path)),
+        TokenValue::Tuple(tokens) => check_tokens(tokens, path),
+        TokenValue::Array(values) | TokenValue::FixedArray(values) => check_values(values, path),
+        TokenValue::Map(_, values) => values.iter().try_for_each(|(key, value)| {
+            path.with(format!("[{}]", key), |path| check_value(value, path))
+        }),
+        TokenValue::Optional(_, Some(value)) | TokenValue::Ref(value) => check_value(value, path),
+        TokenValue::PublicKey(Some(key)) if key.len() != 32 => Err(PackerError::InvalidLength {
+            path: path.to_string(),
+            expected: 32,
+            found: key.len(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn overflow(path: &Path) -> impl FnOnce(Overflow) -> PackerError + '_ {
+    move |Overflow { size }| PackerError::IntegerOverflow {
+        path: path.to_string(),
+        size,
+    }
+}
@@ -0,0 +1,174 @@
+//! Ed25519 public keys and signatures, packed as `uint256` and `bytes`.
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use ton_abi::TokenValue;
+
+use crate::abi_type::IntoUint;
+use crate::{BuildTokenValue, StandaloneToken};
+
+pub const PUBLIC_KEY_LEN: usize = 32;
+pub const SIGNATURE_LEN: usize = 64;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum CryptoError {
+    #[error("Invalid hex encoding")]
+    InvalidHex,
+    #[error("Invalid length (expected {expected} bytes, found {found})")]
+    InvalidLength { expected: usize, found: usize },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PublicKey([u8; PUBLIC_KEY_LEN]);
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Signature([u8; SIGNATURE_LEN]);
+
+macro_rules! impl_bytes_newtype {
+    ($ty:ident, $len:ident) => {
+        impl $ty {
+            pub fn from_bytes(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+
+            pub fn from_slice(bytes: &[u8]) -> Result<Self, CryptoError> {
+                <[u8; $len]>::try_from(bytes)
+                    .map(Self)
+                    .map_err(|_| CryptoError::InvalidLength {
+                        expected: $len,
+                        found: bytes.len(),
+                    })
+            }
+
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            pub fn to_bytes(self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $ty {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $ty {
+            type Error = CryptoError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                Self::from_slice(bytes)
+            }
+        }
+
+        impl TryFrom<Vec<u8>> for $ty {
+            type Error = CryptoError;
+
+            fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+                Self::from_slice(&bytes)
+            }
+        }
+
+        impl AsRef<[u8]> for $ty {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&hex::encode(self.0))
+            }
+        }
+
+        impl std::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($ty), self)
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = CryptoError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let bytes = hex::decode(s).map_err(|_| CryptoError::InvalidHex)?;
+                Self::from_slice(&bytes)
+            }
+        }
+
+        impl StandaloneToken for $ty {}
+    };
+}
+
+impl_bytes_newtype!(PublicKey, PUBLIC_KEY_LEN);
+impl_bytes_newtype!(Signature, SIGNATURE_LEN);
+
+impl BuildTokenValue for PublicKey {
+    fn token_value(self) -> TokenValue {
+        TokenValue::Uint(ton_abi::Uint {
+            number: self.into_uint(),
+            size: 256,
+        })
+    }
+}
+
+impl IntoUint for PublicKey {
+    fn into_uint(self) -> BigUint {
+        BigUint::from_bytes_be(&self.0)
+    }
+}
+
+impl BuildTokenValue for Signature {
+    fn token_value(self) -> TokenValue {
+        TokenValue::Bytes(self.0.to_vec())
+    }
+}
+
+#[cfg(feature = "ed25519")]
+mod ed25519 {
+    use std::convert::TryFrom;
+
+    use super::{PublicKey, Signature};
+
+    impl PublicKey {
+        /// Checks the signature of the message, rejecting weak keys and malleable signatures.
+        pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+            match ed25519_dalek::VerifyingKey::try_from(*self) {
+                Ok(key) => key
+                    .verify_strict(message, &ed25519_dalek::Signature::from(*signature))
+                    .is_ok(),
+                Err(_) => false,
+            }
+        }
+    }
+
+    impl From<ed25519_dalek::VerifyingKey> for PublicKey {
+        fn from(key: ed25519_dalek::VerifyingKey) -> Self {
+            Self(key.to_bytes())
+        }
+    }
+
+    impl TryFrom<PublicKey> for ed25519_dalek::VerifyingKey {
+        type Error = ed25519_dalek::SignatureError;
+
+        fn try_from(key: PublicKey) -> Result<Self, Self::Error> {
+            ed25519_dalek::VerifyingKey::from_bytes(&key.0)
+        }
+    }
+
+    impl From<ed25519_dalek::Signature> for Signature {
+        fn from(signature: ed25519_dalek::Signature) -> Self {
+            Self(signature.to_bytes())
+        }
+    }
+
+    impl From<Signature> for ed25519_dalek::Signature {
+        fn from(signature: Signature) -> Self {
+            ed25519_dalek::Signature::from_bytes(&signature.0)
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Serialization of token values into the JSON format used by `ton_abi`
+//! (decimal strings for integers, `0:hex` addresses, base64 cells).
+//!
+//! Types deriving `PackAbi` with `#[abi(json)]` must also implement `Clone`,
+//! since their `Serialize` impl packs a clone of the value.
+
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use ton_abi::{Token, TokenValue};
+
+pub fn serialize_token_value<S>(value: &TokenValue, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    value.serialize(serializer)
+}
+
+pub fn serialize_tokens<S>(tokens: &[Token], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut map = serializer.serialize_map(Some(tokens.len()))?;
+    for token in tokens {
+        map.serialize_entry(&token.name, &token.value)?;
+    }
+    map.end()
+}
@@ -1,16 +1,64 @@
+#[cfg(feature = "bitflags")]
+pub use bitflags;
 pub use num_bigint;
+pub use serde;
+#[doc(hidden)]
+pub use ton_abi;
+
+pub mod abi_type;
+pub mod address;
+pub mod amount;
+pub mod builder;
+pub mod cell;
+pub mod checked;
+pub mod crypto;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod ser;
+pub mod store;
+mod width;
+pub mod with;
+
+pub use abi_type::AbiCompatible;
+pub use address::{parse_address, AddressError, FriendlyAddress};
+pub use amount::{AmountError, TokenAmount};
+pub use builder::{BuilderError, TokensBuilder};
+pub use checked::{PackerError, TryBuildTokenValue, TryPackTokens};
+pub use crypto::{CryptoError, PublicKey, Signature};
+pub use ser::to_token_value;
+pub use store::StoreBuilder;
+pub use ton_token_core::StandaloneToken;
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use num_bigint::{BigInt, BigUint};
 use ton_abi::{Token, TokenValue};
-use ton_block::{MsgAddrStd, MsgAddress, MsgAddressInt};
+use ton_block::{MsgAddrExt, MsgAddrStd, MsgAddress, MsgAddressInt};
 use ton_types::{Cell, UInt256};
 
+use abi_type::{IntoAddress, IntoInner};
+
 pub trait PackTokens {
     fn pack(self) -> Vec<Token>;
 }
 
+/// Conversion into a token value.
+///
+/// Token values hold integers as `BigUint`, so building one allocates for
+/// every integer. See [`StoreBuilder`] for writing small integers without it.
 pub trait BuildTokenValue {
     fn token_value(self) -> TokenValue;
+
+    /// Builds an array of values, `u8` overrides it to build `bytes`.
+    #[doc(hidden)]
+    fn token_value_vec(values: Vec<Self>) -> TokenValue
+    where
+        Self: Sized,
+    {
+        TokenValue::Array(values.into_iter().map(Self::token_value).collect())
+    }
 }
 
 pub trait BuildTokenValues {
@@ -45,6 +93,10 @@ impl BuildTokenValue for u8 {
             size: 8,
         })
     }
+
+    fn token_value_vec(values: Vec<Self>) -> TokenValue {
+        TokenValue::Bytes(values)
+    }
 }
 
 impl BuildTokenValue for u16 {
@@ -78,29 +130,44 @@ impl BuildTokenValue for u128 {
     fn token_value(self) -> TokenValue {
         TokenValue::Uint(ton_abi::Uint {
             number: BigUint::from(self),
-            size: 64,
+            size: 128,
         })
     }
 }
 
-impl BuildTokenValue for Vec<u8> {
+impl BuildTokenValue for MsgAddrStd {
     fn token_value(self) -> TokenValue {
-        TokenValue::Bytes(self)
+        TokenValue::Address(self.into_address())
     }
 }
 
-impl BuildTokenValue for MsgAddrStd {
+impl BuildTokenValue for MsgAddressInt {
     fn token_value(self) -> TokenValue {
-        TokenValue::Address(MsgAddress::AddrStd(self))
+        TokenValue::Address(self.into_address())
     }
 }
 
-impl BuildTokenValue for MsgAddressInt {
+impl BuildTokenValue for MsgAddrExt {
     fn token_value(self) -> TokenValue {
-        TokenValue::Address(match self {
-            MsgAddressInt::AddrStd(addr) => MsgAddress::AddrStd(addr),
-            MsgAddressInt::AddrVar(addr) => MsgAddress::AddrVar(addr),
-        })
+        TokenValue::Address(self.into_address())
+    }
+}
+
+impl BuildTokenValue for MsgAddress {
+    fn token_value(self) -> TokenValue {
+        TokenValue::Address(self)
+    }
+}
+
+impl BuildTokenValue for FriendlyAddress {
+    fn token_value(self) -> TokenValue {
+        TokenValue::Address(self.into_address())
+    }
+}
+
+impl BuildTokenValue for Option<MsgAddressInt> {
+    fn token_value(self) -> TokenValue {
+        TokenValue::Address(self.into_address())
     }
 }
 
@@ -121,10 +188,10 @@ impl BuildTokenValue for UInt256 {
 
 impl<T> BuildTokenValue for Vec<T>
 where
-    T: StandaloneToken + BuildTokenValue,
+    T: BuildTokenValue,
 {
     fn token_value(self) -> TokenValue {
-        TokenValue::Array(self.into_iter().map(BuildTokenValue::token_value).collect())
+        T::token_value_vec(self)
     }
 }
 
@@ -143,6 +210,43 @@ where
     }
 }
 
+impl<T> BuildTokenValue for Box<T>
+where
+    T: BuildTokenValue,
+{
+    fn token_value(self) -> TokenValue {
+        self.into_inner().token_value()
+    }
+}
+
+impl<T> BuildTokenValue for Rc<T>
+where
+    T: Clone + BuildTokenValue,
+{
+    fn token_value(self) -> TokenValue {
+        self.into_inner().token_value()
+    }
+}
+
+impl<T> BuildTokenValue for Arc<T>
+where
+    T: Clone + BuildTokenValue,
+{
+    fn token_value(self) -> TokenValue {
+        self.into_inner().token_value()
+    }
+}
+
+impl<B> BuildTokenValue for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: BuildTokenValue,
+{
+    fn token_value(self) -> TokenValue {
+        self.into_inner().token_value()
+    }
+}
+
 impl<T> BuildTokenValues for &T
 where
     T: Clone + BuildTokenValues,
@@ -152,14 +256,62 @@ where
     }
 }
 
-pub trait StandaloneToken {}
-impl StandaloneToken for MsgAddressInt {}
-impl StandaloneToken for MsgAddrStd {}
-impl StandaloneToken for UInt256 {}
-impl StandaloneToken for u16 {}
-impl StandaloneToken for u32 {}
-impl StandaloneToken for u64 {}
-impl StandaloneToken for u128 {}
-impl StandaloneToken for bool {}
-impl StandaloneToken for Vec<u8> {}
-impl StandaloneToken for TokenValue {}
+impl<T> BuildTokenValues for Vec<T>
+where
+    T: BuildTokenValue,
+{
+    fn token_values(self) -> Vec<TokenValue> {
+        self.into_iter().map(BuildTokenValue::token_value).collect()
+    }
+}
+
+impl<T, const N: usize> BuildTokenValues for [T; N]
+where
+    T: BuildTokenValue,
+{
+    fn token_values(self) -> Vec<TokenValue> {
+        IntoIterator::into_iter(self)
+            .map(BuildTokenValue::token_value)
+            .collect()
+    }
+}
+
+macro_rules! impl_build_token_values_for_tuple {
+    ($($ty:ident $var:ident),+) => {
+        impl<$($ty),+> BuildTokenValues for ($($ty,)+)
+        where
+            $($ty: BuildTokenValue),+
+        {
+            fn token_values(self) -> Vec<TokenValue> {
+                let ($($var,)+) = self;
+                vec![$($var.token_value()),+]
+            }
+        }
+    };
+}
+
+impl_build_token_values_for_tuple!(A a);
+impl_build_token_values_for_tuple!(A a, B b);
+impl_build_token_values_for_tuple!(A a, B b, C c);
+impl_build_token_values_for_tuple!(A a, B b, C c, D d);
+impl_build_token_values_for_tuple!(A a, B b, C c, D d, E e);
+impl_build_token_values_for_tuple!(A a, B b, C c, D d, E e, F f);
+impl_build_token_values_for_tuple!(A a, B b, C c, D d, E e, F f, G g);
+impl_build_token_values_for_tuple!(A a, B b, C c, D d, E e, F f, G g, H h);
+
+impl StandaloneToken for FriendlyAddress {}
+
+/// Implements [`BuildTokenValue`] and [`StandaloneToken`] for a `bitflags` type.
+#[cfg(feature = "bitflags")]
+#[macro_export]
+macro_rules! impl_bitflags {
+    ($ty:ty) => {
+        impl $crate::BuildTokenValue for $ty {
+            fn token_value(self) -> $crate::ton_abi::TokenValue {
+                $crate::with::bitflags::token_value(self)
+            }
+        }
+
+        impl $crate::StandaloneToken for $ty {}
+    };
+}
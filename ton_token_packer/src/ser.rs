@@ -0,0 +1,466 @@
+//! `serde` serializer producing token values for types that don't derive `PackAbi`.
+//!
+//! Serde data model doesn't carry ABI types, so the serializer is guided by
+//! a `ParamType` schema: it decides integer widths, whether a string is an
+//! address or bytes, and the order and names of tuple components.
+
+use std::str::FromStr;
+
+use num_bigint::{BigInt, BigUint};
+use serde::ser::{self, Serialize};
+use ton_abi::{Param, ParamType, Token, TokenValue};
+use ton_block::MsgAddress;
+
+use crate::abi_type::IntoAddress;
+use crate::address::parse_address;
+use crate::width::{check_int, check_uint, var_bits, Overflow};
+
+pub fn to_token_value<T>(value: &T, param_type: &ParamType) -> Result<TokenValue, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer { param_type })
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Error {
+    #[error("{0}")]
+    Custom(String),
+    #[error("Type mismatch (expected {expected}, found {found})")]
+    TypeMismatch {
+        expected: String,
+        found: &'static str,
+    },
+    #[error("Integer {value} doesn't fit into {size} bits")]
+    IntegerOverflow { value: String, size: usize },
+    #[error("Invalid address {0:?}")]
+    InvalidAddress(String),
+    #[error("Missing field {0:?}")]
+    MissingField(String),
+    #[error("Unknown field {0:?}")]
+    UnknownField(String),
+    #[error("Unsupported type: {0}")]
+    Unsupported(&'static str),
+}
+
+impl ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::Custom(msg.to_string())
+    }
+}
+
+static BYTE: ParamType = ParamType::Uint(8);
+
+struct Serializer<'a> {
+    param_type: &'a ParamType,
+}
+
+impl<'a> Serializer<'a> {
+    fn mismatch(&self, found: &'static str) -> Error {
+        Error::TypeMismatch {
+            expected: self.param_type.type_signature(),
+            found,
+        }
+    }
+
+    fn serialize_uint(self, number: BigUint) -> Result<TokenValue, Error> {
+        match *self.param_type {
+            ParamType::Uint(size) => {
+                check_uint(&number, size).map_err(|e| overflow(&number, e))?;
+                Ok(TokenValue::Uint(ton_abi::Uint { number, size }))
+            }
+            ParamType::VarUint(size) => {
+                var_bits(size)
+                    .and_then(|bits| check_uint(&number, bits))
+                    .map_err(|e| overflow(&number, e))?;
+                Ok(TokenValue::VarUint(size, number))
+            }
+            ParamType::Int(_) | ParamType::VarInt(_) => self.serialize_int(BigInt::from(number)),
+            _ => Err(self.mismatch("unsigned integer")),
+        }
+    }
+
+    fn serialize_int(self, number: BigInt) -> Result<TokenValue, Error> {
+        match *self.param_type {
+            ParamType::Int(size) => {
+                check_int(&number, size).map_err(|e| overflow(&number, e))?;
+                Ok(TokenValue::Int(ton_abi::Int { number, size }))
+            }
+            ParamType::VarInt(size) => {
+                var_bits(size)
+                    .and_then(|bits| check_int(&number, bits))
+                    .map_err(|e| overflow(&number, e))?;
+                Ok(TokenValue::VarInt(size, number))
+            }
+            ParamType::Uint(_) | ParamType::VarUint(_) => match number.to_biguint() {
+                Some(number) => self.serialize_uint(number),
+                None => Err(self.mismatch("negative integer")),
+            },
+            _ => Err(self.mismatch("signed integer")),
+        }
+    }
+}
+
+fn overflow<N>(number: &N, Overflow { size }: Overflow) -> Error
+where
+    N: std::fmt::Display,
+{
+    Error::IntegerOverflow {
+        value: number.to_string(),
+        size,
+    }
+}
+
+fn params_of(param_type: &ParamType) -> Option<&[Param]> {
+    match param_type {
+        ParamType::Tuple(params) => Some(params),
+        _ => None,
+    }
+}
+
+macro_rules! serialize_unsigned {
+    ($($method:ident: $ty:ty),*) => {
+        $(fn $method(self, v: $ty) -> Result<TokenValue, Error> {
+            self.serialize_uint(BigUint::from(v))
+        })*
+    };
+}
+
+macro_rules! serialize_signed {
+    ($($method:ident: $ty:ty),*) => {
+        $(fn $method(self, v: $ty) -> Result<TokenValue, Error> {
+            self.serialize_int(BigInt::from(v))
+        })*
+    };
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = TokenValue;
+    type Error = Error;
+
+    type SerializeSeq = SerializeSeq<'a>;
+    type SerializeTuple = SerializeTuple<'a>;
+    type SerializeTupleStruct = SerializeTuple<'a>;
+    type SerializeTupleVariant = ser::Impossible<TokenValue, Error>;
+    type SerializeMap = ser::Impossible<TokenValue, Error>;
+    type SerializeStruct = SerializeStruct<'a>;
+    type SerializeStructVariant = ser::Impossible<TokenValue, Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<TokenValue, Error> {
+        match self.param_type {
+            ParamType::Bool => Ok(TokenValue::Bool(v)),
+            _ => Err(self.mismatch("bool")),
+        }
+    }
+
+    serialize_unsigned!(serialize_u8: u8, serialize_u16: u16, serialize_u32: u32, serialize_u64: u64, serialize_u128: u128);
+    serialize_signed!(serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64, serialize_i128: i128);
+
+    fn serialize_f32(self, _: f32) -> Result<TokenValue, Error> {
+        Err(Error::Unsupported("f32"))
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<TokenValue, Error> {
+        Err(Error::Unsupported("f64"))
+    }
+
+    fn serialize_char(self, v: char) -> Result<TokenValue, Error> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<TokenValue, Error> {
+        match self.param_type {
+            ParamType::String => Ok(TokenValue::String(v.to_string())),
+            ParamType::Bytes => Ok(TokenValue::Bytes(v.as_bytes().to_vec())),
+            ParamType::Address => match parse_address(v) {
+                Ok(address) => Ok(TokenValue::Address(address.into_address())),
+                Err(_) => Err(Error::InvalidAddress(v.to_string())),
+            },
+            ParamType::Uint(_) | ParamType::VarUint(_) => match BigUint::from_str(v) {
+                Ok(number) => self.serialize_uint(number),
+                Err(_) => Err(self.mismatch("string")),
+            },
+            ParamType::Int(_) | ParamType::VarInt(_) => match BigInt::from_str(v) {
+                Ok(number) => self.serialize_int(number),
+                Err(_) => Err(self.mismatch("string")),
+            },
+            _ => Err(self.mismatch("string")),
+        }
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<TokenValue, Error> {
+        match *self.param_type {
+            ParamType::Bytes => Ok(TokenValue::Bytes(v.to_vec())),
+            ParamType::FixedBytes(len) if len == v.len() => Ok(TokenValue::FixedBytes(v.to_vec())),
+            _ => Err(self.mismatch("bytes")),
+        }
+    }
+
+    fn serialize_none(self) -> Result<TokenValue, Error> {
+        match self.param_type {
+            ParamType::Optional(inner) => Ok(TokenValue::Optional(inner.as_ref().clone(), None)),
+            ParamType::Address => Ok(TokenValue::Address(MsgAddress::AddrNone)),
+            _ => Err(self.mismatch("none")),
+        }
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<TokenValue, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self.param_type {
+            ParamType::Optional(inner) => Ok(TokenValue::Optional(
+                inner.as_ref().clone(),
+                Some(Box::new(to_token_value(value, inner)?)),
+            )),
+            _ => value.serialize(self),
+        }
+    }
+
+    fn serialize_unit(self) -> Result<TokenValue, Error> {
+        match params_of(self.param_type) {
+            Some([]) => Ok(TokenValue::Tuple(Vec::new())),
+            _ => Err(self.mismatch("unit")),
+        }
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<TokenValue, Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<TokenValue, Error> {
+        match self.param_type {
+            ParamType::String | ParamType::Bytes => self.serialize_str(variant),
+            _ => self.serialize_u32(variant_index),
+        }
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<TokenValue, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<TokenValue, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(Error::Unsupported("newtype variant"))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeSeq<'a>, Error> {
+        let (item_type, bytes) = match self.param_type {
+            ParamType::Array(item_type) | ParamType::FixedArray(item_type, _) => {
+                (item_type.as_ref(), false)
+            }
+            ParamType::Bytes | ParamType::FixedBytes(_) => (&BYTE, true),
+            _ => return Err(self.mismatch("sequence")),
+        };
+        Ok(SerializeSeq {
+            param_type: self.param_type,
+            item_type,
+            bytes,
+            items: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeTuple<'a>, Error> {
+        match params_of(self.param_type) {
+            Some(params) if params.len() == len => Ok(SerializeTuple {
+                params,
+                tokens: Vec::with_capacity(len),
+            }),
+            _ => Err(self.mismatch("tuple")),
+        }
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeTuple<'a>, Error> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::Unsupported("tuple variant"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::Unsupported("map"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStruct<'a>, Error> {
+        match params_of(self.param_type) {
+            Some(params) => Ok(SerializeStruct {
+                params,
+                values: vec![None; params.len()],
+            }),
+            None => Err(self.mismatch("struct")),
+        }
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::Unsupported("struct variant"))
+    }
+}
+
+pub struct SerializeSeq<'a> {
+    param_type: &'a ParamType,
+    item_type: &'a ParamType,
+    bytes: bool,
+    items: Vec<TokenValue>,
+}
+
+impl<'a> ser::SerializeSeq for SerializeSeq<'a> {
+    type Ok = TokenValue;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.items.push(to_token_value(value, self.item_type)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<TokenValue, Error> {
+        let len = self.items.len();
+        if !self.bytes {
+            return match *self.param_type {
+                ParamType::FixedArray(_, size) if size != len => Err(Error::TypeMismatch {
+                    expected: self.param_type.type_signature(),
+                    found: "sequence of different length",
+                }),
+                ParamType::FixedArray(..) => Ok(TokenValue::FixedArray(self.items)),
+                _ => Ok(TokenValue::Array(self.items)),
+            };
+        }
+
+        let bytes = self
+            .items
+            .into_iter()
+            .map(|item| match item {
+                TokenValue::Uint(ton_abi::Uint { number, .. }) => {
+                    Ok(number.to_bytes_le().first().copied().unwrap_or_default())
+                }
+                _ => Err(Error::Unsupported("non-byte sequence item")),
+            })
+            .collect::<Result<Vec<u8>, Error>>()?;
+        match *self.param_type {
+            ParamType::FixedBytes(size) if size != len => Err(Error::TypeMismatch {
+                expected: self.param_type.type_signature(),
+                found: "sequence of different length",
+            }),
+            ParamType::FixedBytes(_) => Ok(TokenValue::FixedBytes(bytes)),
+            _ => Ok(TokenValue::Bytes(bytes)),
+        }
+    }
+}
+
+pub struct SerializeTuple<'a> {
+    params: &'a [Param],
+    tokens: Vec<Token>,
+}
+
+impl<'a> ser::SerializeTuple for SerializeTuple<'a> {
+    type Ok = TokenValue;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let param = &self.params[self.tokens.len()];
+        let value = to_token_value(value, &param.kind)?;
+        self.tokens.push(Token::new(&param.name, value));
+        Ok(())
+    }
+
+    fn end(self) -> Result<TokenValue, Error> {
+        Ok(TokenValue::Tuple(self.tokens))
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for SerializeTuple<'a> {
+    type Ok = TokenValue;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeTuple::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<TokenValue, Error> {
+        ser::SerializeTuple::end(self)
+    }
+}
+
+pub struct SerializeStruct<'a> {
+    params: &'a [Param],
+    values: Vec<Option<TokenValue>>,
+}
+
+impl<'a> ser::SerializeStruct for SerializeStruct<'a> {
+    type Ok = TokenValue;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let index = self
+            .params
+            .iter()
+            .position(|param| param.name == key)
+            .ok_or_else(|| Error::UnknownField(key.to_string()))?;
+        self.values[index] = Some(to_token_value(value, &self.params[index].kind)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<TokenValue, Error> {
+        self.params
+            .iter()
+            .zip(self.values)
+            .map(|(param, value)| match value {
+                Some(value) => Ok(Token::new(&param.name, value)),
+                None => Err(Error::MissingField(param.name.clone())),
+            })
+            .collect::<Result<Vec<Token>, Error>>()
+            .map(TokenValue::Tuple)
+    }
+}
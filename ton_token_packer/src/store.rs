@@ -0,0 +1,44 @@
+//! Writing of small integer fields straight into a cell builder.
+//!
+//! Tokens hold every integer as a `BigUint` and every tuple component under
+//! its own name, so building them allocates per field. Structs deriving
+//! `PackAbi` with `#[abi(store)]` implement [`StoreBuilder`] by storing each
+//! field in turn, which requires every field type to implement it as well:
+//! integers of up to 128 bits, bools and other `#[abi(store)]` structs. The
+//! written bits are the same as the ABI encoder's. Values which don't fit into
+//! the builder are rejected instead of being continued in a new cell.
+
+pub use ton_types::BuilderData;
+
+use crate::PackerError;
+
+pub trait StoreBuilder {
+    fn store_into(&self, builder: &mut BuilderData) -> Result<(), PackerError>;
+}
+
+macro_rules! impl_store_builder_int {
+    ($($ty:ty),*) => {
+        $(
+            impl StoreBuilder for $ty {
+                fn store_into(&self, builder: &mut BuilderData) -> Result<(), PackerError> {
+                    append(builder, &self.to_be_bytes(), std::mem::size_of::<$ty>() * 8)
+                }
+            }
+        )*
+    };
+}
+
+impl_store_builder_int!(i8, u8, u16, u32, u64, u128);
+
+impl StoreBuilder for bool {
+    fn store_into(&self, builder: &mut BuilderData) -> Result<(), PackerError> {
+        append(builder, &[(*self as u8) << 7], 1)
+    }
+}
+
+fn append(builder: &mut BuilderData, data: &[u8], bits: usize) -> Result<(), PackerError> {
+    builder
+        .append_raw(data, bits)
+        .map(|_| ())
+        .map_err(|e| PackerError::InvalidCell(e.to_string()))
+}
@@ -0,0 +1,33 @@
+//! Width checks shared by the checked packer and the serializer.
+//!
+//! A zero-width integer has no room for a value, so even zero is rejected.
+
+use num_bigint::{BigInt, BigUint};
+
+/// Integer which doesn't fit into `size` bits.
+pub(crate) struct Overflow {
+    pub size: usize,
+}
+
+/// Width of the value of a `varuint`/`varint` of `size` bytes, one of which holds the length.
+pub(crate) fn var_bits(size: usize) -> Result<usize, Overflow> {
+    match size.checked_sub(1) {
+        Some(len) => Ok(len * 8),
+        None => Err(Overflow { size: 0 }),
+    }
+}
+
+pub(crate) fn check_uint(number: &BigUint, size: usize) -> Result<(), Overflow> {
+    if size == 0 || number.bits() > size {
+        return Err(Overflow { size });
+    }
+    Ok(())
+}
+
+pub(crate) fn check_int(number: &BigInt, size: usize) -> Result<(), Overflow> {
+    // `bits` ignores the sign, so the only value of full width is the minimum one
+    if size == 0 || number.bits() >= size && *number != -(BigInt::from(1) << (size - 1)) {
+        return Err(Overflow { size });
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! Packing halves of ready-made `#[abi(with = "...")]` adapters.
+//!
+//! An adapter is a module with `pack(T) -> Result<TokenValue, PackerError>`
+//! and `unpack(&TokenValue) -> ContractResult<T>` functions. Types deriving
+//! `PackAbi` with `#[abi(checked)]` report `pack` errors, the infallible
+//! derive panics on them.
+//!
+//! The matching `unpack` functions live under the same paths in
+//! `ton_token_unpacker::with`, `ton_token::with` combines both halves into
+//! modules which can be used by types deriving both `PackAbi` and `UnpackAbi`.
+
+/// `String` with hex encoded data, packed as `bytes`.
+pub mod hex {
+    use ton_abi::TokenValue;
+
+    use crate::checked::ROOT;
+    use crate::PackerError;
+
+    pub fn pack(value: String) -> Result<TokenValue, PackerError> {
+        match ::hex::decode(&value) {
+            Ok(bytes) => Ok(TokenValue::Bytes(bytes)),
+            Err(e) => Err(PackerError::InvalidValue {
+                path: ROOT.to_owned(),
+                reason: format!("invalid hex string: {}", e),
+            }),
+        }
+    }
+}
+
+/// `SystemTime`, packed as `uint32` unix timestamp in seconds.
+pub mod unix_time {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use num_bigint::BigUint;
+    use ton_abi::TokenValue;
+
+    use crate::checked::ROOT;
+    use crate::PackerError;
+
+    /// Times earlier than the unix epoch or later than `u32::MAX` seconds
+    /// after it are reported as [`PackerError::IntegerOverflow`].
+    pub fn pack(value: SystemTime) -> Result<TokenValue, PackerError> {
+        let seconds = value
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|duration| u32::try_from(duration.as_secs()).ok())
+            .ok_or_else(|| PackerError::IntegerOverflow {
+                path: ROOT.to_owned(),
+                size: 32,
+            })?;
+
+        Ok(TokenValue::Uint(ton_abi::Uint {
+            number: BigUint::from(seconds),
+            size: 32,
+        }))
+    }
+}
+
+/// `TokenAmount<9>`, packed as `uint128` nanotons.
+pub mod nanotons {
+    use ton_abi::TokenValue;
+
+    use crate::amount::TokenAmount;
+    use crate::{BuildTokenValue, PackerError};
+
+    pub fn pack(value: TokenAmount<9>) -> Result<TokenValue, PackerError> {
+        Ok(value.token_value())
+    }
+}
+
+/// Any amount convertible to `u128`, packed as `varuint16`.
+pub mod varuint16 {
+    use num_bigint::BigUint;
+    use ton_abi::TokenValue;
+
+    use crate::PackerError;
+
+    pub fn pack<T>(value: T) -> Result<TokenValue, PackerError>
+    where
+        T: Into<u128>,
+    {
+        Ok(TokenValue::VarUint(16, BigUint::from(value.into())))
+    }
+}
+
+/// Any `bitflags` type, packed as unsigned integer of the same width as its bits.
+#[cfg(feature = "bitflags")]
+pub mod bitflags {
+    use bitflags::Flags;
+    use num_bigint::BigUint;
+    use ton_abi::TokenValue;
+
+    use crate::PackerError;
+
+    pub fn pack<T>(value: T) -> Result<TokenValue, PackerError>
+    where
+        T: Flags,
+        T::Bits: Into<BigUint>,
+    {
+        Ok(token_value(value))
+    }
+
+    /// Infallible version of [`pack`], used by [`impl_bitflags!`](crate::impl_bitflags).
+    #[doc(hidden)]
+    pub fn token_value<T>(value: T) -> TokenValue
+    where
+        T: Flags,
+        T::Bits: Into<BigUint>,
+    {
+        TokenValue::Uint(ton_abi::Uint {
+            number: value.bits().into(),
+            size: std::mem::size_of::<T::Bits>() * 8,
+        })
+    }
+}
@@ -0,0 +1,318 @@
+//! Conversions of `#[abi(type)]` fields, the markers are shared with
+//! `ton_token_packer` through `ton_token_core`.
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub use ton_token_core::abi_type::{
+    AbiCompatible, Address, Biguint128, Bool, Cell, Int8, Uint128, Uint16, Uint160, Uint256,
+    Uint32, Uint64, Uint8,
+};
+
+/// Conversion of fields with an `#[abi(type)]` attribute, see `ton_token_packer::abi_type::IntoAbi`.
+#[diagnostic::on_unimplemented(
+    message = "field type `{Self}` cannot be decoded from `{A}`",
+    label = "incompatible with the `#[abi]` type"
+)]
+pub trait FromAbi<A>: AbiCompatible<A> + Sized {
+    fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self>;
+}
+
+macro_rules! impl_from_abi_primitive {
+    ($($ty:ty => $marker:ty, $variant:ident, $size:literal, $convert:ident;)*) => {
+        $(
+            #[diagnostic::do_not_recommend]
+            impl FromAbi<$marker> for $ty {
+                fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self> {
+                    match value {
+                        ton_abi::TokenValue::$variant(ton_abi::$variant { number, size: $size }) => {
+                            num_traits::ToPrimitive::$convert(&number)
+                                .ok_or(crate::UnpackerError::InvalidAbi)
+                        }
+                        _ => Err(crate::UnpackerError::InvalidAbi),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_abi_uint {
+    ($($ty:ty => $marker:ty, $size:literal;)*) => {
+        $(
+            #[diagnostic::do_not_recommend]
+            impl FromAbi<$marker> for $ty {
+                fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self> {
+                    match value {
+                        ton_abi::TokenValue::Uint(ton_abi::Uint { number, size: $size }) => {
+                            FromUint::from_uint(number, $size)
+                        }
+                        _ => Err(crate::UnpackerError::InvalidAbi),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// `uint256` fields accept numbers of any width, like the plain `UInt256` token.
+macro_rules! impl_from_abi_uint256 {
+    ($($ty:ty),*) => {
+        $(
+            #[diagnostic::do_not_recommend]
+            impl FromAbi<Uint256> for $ty {
+                fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self> {
+                    match value {
+                        ton_abi::TokenValue::Uint(ton_abi::Uint { number, .. }) => {
+                            FromUint::from_uint(number, 256)
+                        }
+                        _ => Err(crate::UnpackerError::InvalidAbi),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_abi_address {
+    ($($ty:ty),*) => {
+        $(
+            #[diagnostic::do_not_recommend]
+            impl FromAbi<Address> for $ty {
+                fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self> {
+                    match value {
+                        ton_abi::TokenValue::Address(address) => FromAddress::from_address(address),
+                        _ => Err(crate::UnpackerError::InvalidAbi),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_abi_primitive! {
+    i8 => Int8, Int, 8, to_i8;
+    u8 => Uint8, Uint, 8, to_u8;
+    u16 => Uint16, Uint, 16, to_u16;
+    u32 => Uint32, Uint, 32, to_u32;
+    u64 => Uint64, Uint, 64, to_u64;
+    u128 => Uint128, Uint, 128, to_u128;
+}
+
+impl_from_abi_uint! {
+    num_bigint::BigUint => Uint160, 160;
+    [u8; 20] => Uint160, 160;
+    Vec<u8> => Uint160, 160;
+    num_bigint::BigUint => Biguint128, 128;
+    [u8; 16] => Biguint128, 128;
+    Vec<u8> => Biguint128, 128;
+}
+
+impl_from_abi_uint256!(
+    num_bigint::BigUint,
+    ton_types::UInt256,
+    [u8; 32],
+    Vec<u8>,
+    crate::PublicKey
+);
+
+impl_from_abi_address!(
+    ton_block::MsgAddress,
+    ton_block::MsgAddressInt,
+    ton_block::MsgAddrStd,
+    ton_block::MsgAddrExt,
+    crate::FriendlyAddress,
+    String
+);
+
+#[diagnostic::do_not_recommend]
+impl<T> FromAbi<Address> for Option<T>
+where
+    T: FromAbi<Address> + FromAddress,
+{
+    fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self> {
+        match value {
+            ton_abi::TokenValue::Address(address) => FromAddress::from_address(address),
+            _ => Err(crate::UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+#[diagnostic::do_not_recommend]
+impl FromAbi<Bool> for bool {
+    fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self> {
+        match value {
+            ton_abi::TokenValue::Bool(value) => Ok(value),
+            _ => Err(crate::UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+#[diagnostic::do_not_recommend]
+impl FromAbi<Cell> for ton_types::Cell {
+    fn from_abi(value: ton_abi::TokenValue) -> crate::ContractResult<Self> {
+        match value {
+            ton_abi::TokenValue::Cell(cell) => Ok(cell),
+            _ => Err(crate::UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+/// Smart pointers around `#[abi(type)]` fields, wrapped after unpacking.
+pub trait FromInner {
+    type Inner;
+
+    fn from_inner(inner: Self::Inner) -> Self;
+}
+
+impl<T> FromInner for Box<T> {
+    type Inner = T;
+
+    fn from_inner(inner: T) -> Self {
+        Box::new(inner)
+    }
+}
+
+impl<T> FromInner for Rc<T> {
+    type Inner = T;
+
+    fn from_inner(inner: T) -> Self {
+        Rc::new(inner)
+    }
+}
+
+impl<T> FromInner for Arc<T> {
+    type Inner = T;
+
+    fn from_inner(inner: T) -> Self {
+        Arc::new(inner)
+    }
+}
+
+impl<B> FromInner for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+{
+    type Inner = B::Owned;
+
+    fn from_inner(inner: B::Owned) -> Self {
+        Cow::Owned(inner)
+    }
+}
+
+/// Conversion of wide unsigned integer fields (`uint160`, `uint256`, `biguint128`).
+pub trait FromUint: Sized {
+    fn from_uint(number: num_bigint::BigUint, size: usize) -> crate::ContractResult<Self>;
+}
+
+impl FromUint for num_bigint::BigUint {
+    fn from_uint(number: num_bigint::BigUint, _: usize) -> crate::ContractResult<Self> {
+        Ok(number)
+    }
+}
+
+impl FromUint for ton_types::UInt256 {
+    fn from_uint(number: num_bigint::BigUint, size: usize) -> crate::ContractResult<Self> {
+        <[u8; 32]>::from_uint(number, size).map(Self::from)
+    }
+}
+
+/// Big-endian bytes, left-padded with zeros.
+impl<const N: usize> FromUint for [u8; N] {
+    fn from_uint(number: num_bigint::BigUint, _: usize) -> crate::ContractResult<Self> {
+        let bytes = number.to_bytes_be();
+        if bytes.len() > N {
+            return Err(crate::UnpackerError::InvalidAbi);
+        }
+
+        let mut result = [0; N];
+        result[N - bytes.len()..].copy_from_slice(&bytes);
+        Ok(result)
+    }
+}
+
+/// Big-endian bytes, left-padded with zeros to the width of the type.
+impl FromUint for Vec<u8> {
+    fn from_uint(number: num_bigint::BigUint, size: usize) -> crate::ContractResult<Self> {
+        let bytes = number.to_bytes_be();
+        let len = size / 8;
+        if bytes.len() > len {
+            return Err(crate::UnpackerError::InvalidAbi);
+        }
+
+        let mut result = vec![0; len - bytes.len()];
+        result.extend_from_slice(&bytes);
+        Ok(result)
+    }
+}
+
+impl FromUint for crate::PublicKey {
+    fn from_uint(number: num_bigint::BigUint, size: usize) -> crate::ContractResult<Self> {
+        <[u8; 32]>::from_uint(number, size).map(Self::from_bytes)
+    }
+}
+
+/// Conversion of `address` fields. `addr_none` is unpacked as `None`.
+pub trait FromAddress: Sized {
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self>;
+}
+
+impl FromAddress for ton_block::MsgAddress {
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self> {
+        Ok(address)
+    }
+}
+
+impl FromAddress for ton_block::MsgAddressInt {
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self> {
+        match address {
+            ton_block::MsgAddress::AddrStd(addr) => Ok(ton_block::MsgAddressInt::AddrStd(addr)),
+            ton_block::MsgAddress::AddrVar(addr) => Ok(ton_block::MsgAddressInt::AddrVar(addr)),
+            _ => Err(crate::UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+impl FromAddress for ton_block::MsgAddrStd {
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self> {
+        match address {
+            ton_block::MsgAddress::AddrStd(addr) => Ok(addr),
+            _ => Err(crate::UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+impl FromAddress for ton_block::MsgAddrExt {
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self> {
+        match address {
+            ton_block::MsgAddress::AddrExt(addr) => Ok(addr),
+            _ => Err(crate::UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+impl FromAddress for ton_token_packer::FriendlyAddress {
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self> {
+        ton_block::MsgAddrStd::from_address(address).map(Self::new)
+    }
+}
+
+/// Internal addresses in raw form, e.g. `0:3333...`, see `FriendlyAddress` for the user-friendly one.
+impl FromAddress for String {
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self> {
+        ton_block::MsgAddressInt::from_address(address).map(|address| address.to_string())
+    }
+}
+
+impl<T> FromAddress for Option<T>
+where
+    T: FromAddress,
+{
+    fn from_address(address: ton_block::MsgAddress) -> crate::ContractResult<Self> {
+        match address {
+            ton_block::MsgAddress::AddrNone => Ok(None),
+            address => T::from_address(address).map(Some),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Unpacking of values stored in a separate cell, see `#[abi(cell_of = "...")]`,
+//! and of plain structs from cells and bags of cells.
+
+use ton_abi::{Param, ParamType, TokenValue};
+use ton_types::{Cell, SliceData};
+
+use crate::{
+    ContractResult, KnownParamType, KnownParamTypePlain, UnpackToken, UnpackTokens, UnpackerError,
+};
+
+pub use ton_token_core::ABI_VERSION;
+
+/// Decodes the value from the cell. Tuples are read component-wise.
+pub fn unpack_cell<T>(cell: Cell, abi_version: u8) -> ContractResult<T>
+where
+    T: KnownParamType,
+    TokenValue: UnpackToken<T>,
+{
+    let param_type = T::param_type();
+    let params = match &param_type {
+        ParamType::Tuple(params) => params.clone(),
+        _ => vec![Param::new("value", param_type.clone())],
+    };
+
+    let mut tokens = TokenValue::decode_params(&params, SliceData::from(cell), abi_version)
+        .map_err(|e| UnpackerError::InvalidCell(e.to_string()))?;
+    let value = match param_type {
+        ParamType::Tuple(_) => TokenValue::Tuple(tokens),
+        _ => tokens.pop().ok_or(UnpackerError::InvalidAbi)?.value,
+    };
+    value.unpack()
+}
+
+/// Decodes the tokens of a plain struct from the cell.
+pub fn from_cell<T>(cell: Cell, abi_version: u8) -> ContractResult<T>
+where
+    T: KnownParamTypePlain + UnpackTokens,
+{
+    let tokens = TokenValue::decode_params(&T::param_type(), SliceData::from(cell), abi_version)
+        .map_err(|e| UnpackerError::InvalidCell(e.to_string()))?;
+    T::unpack_tokens(&mut tokens.into_iter())
+}
+
+/// Decodes the tokens of a plain struct from a base64 encoded bag of cells.
+pub fn from_boc_base64<T>(boc: &str, abi_version: u8) -> ContractResult<T>
+where
+    T: KnownParamTypePlain + UnpackTokens,
+{
+    let boc = base64::decode(boc).map_err(|e| UnpackerError::InvalidCell(e.to_string()))?;
+    let cell = ton_types::deserialize_tree_of_cells(&mut boc.as_slice())
+        .map_err(|e| UnpackerError::InvalidCell(e.to_string()))?;
+    from_cell(cell, abi_version)
+}
@@ -0,0 +1,243 @@
+//! `serde` deserializer reading token values into types that don't derive `UnpackAbi`.
+//!
+//! Token values already carry their ABI types, so unlike the serializer no
+//! schema is needed: integers are visited as the smallest fitting primitive
+//! (or as a decimal string), tuples as maps of component names, addresses as
+//! strings and cells as serialized bags of cells.
+
+use std::str::FromStr;
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::ToPrimitive;
+use serde::de::value::{MapDeserializer, SeqDeserializer, StrDeserializer, U32Deserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use ton_abi::TokenValue;
+
+pub fn from_token_value<T>(value: TokenValue) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer(value))
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Error {
+    #[error("{0}")]
+    Custom(String),
+    #[error("Unexpected token value (expected {expected})")]
+    UnexpectedValue { expected: &'static str },
+}
+
+impl de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::Custom(msg.to_string())
+    }
+}
+
+struct Deserializer(TokenValue);
+
+impl<'de> IntoDeserializer<'de, Error> for Deserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn visit_uint<'de, V>(number: BigUint, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    if let Some(number) = number.to_u64() {
+        visitor.visit_u64(number)
+    } else if let Some(number) = number.to_u128() {
+        visitor.visit_u128(number)
+    } else {
+        visitor.visit_string(number.to_string())
+    }
+}
+
+fn visit_int<'de, V>(number: BigInt, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    if let Some(number) = number.to_i64() {
+        visitor.visit_i64(number)
+    } else if let Some(number) = number.to_i128() {
+        visitor.visit_i128(number)
+    } else {
+        visitor.visit_string(number.to_string())
+    }
+}
+
+fn visit_seq<'de, V>(items: Vec<TokenValue>, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(items.into_iter().map(Deserializer));
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+fn visit_bytes_seq<'de, V>(bytes: Vec<u8>, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(bytes.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            TokenValue::Uint(int) => visit_uint(int.number, visitor),
+            TokenValue::VarUint(_, number) => visit_uint(number, visitor),
+            TokenValue::Int(int) => visit_int(int.number, visitor),
+            TokenValue::VarInt(_, number) => visit_int(number, visitor),
+            TokenValue::Gram(grams) => match BigUint::from_str(&grams.to_string()) {
+                Ok(number) => visit_uint(number, visitor),
+                Err(_) => Err(Error::UnexpectedValue { expected: "grams" }),
+            },
+            TokenValue::Time(time) => visitor.visit_u64(time),
+            TokenValue::Expire(expire) => visitor.visit_u32(expire),
+            TokenValue::Bool(value) => visitor.visit_bool(value),
+            TokenValue::Tuple(tokens) => {
+                let mut map = MapDeserializer::new(
+                    tokens
+                        .into_iter()
+                        .map(|token| (token.name, Deserializer(token.value))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            TokenValue::Map(_, values) => {
+                let mut map = MapDeserializer::new(
+                    values
+                        .into_iter()
+                        .map(|(key, value)| (key, Deserializer(value))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            TokenValue::Array(items) | TokenValue::FixedArray(items) => visit_seq(items, visitor),
+            TokenValue::Bytes(bytes) | TokenValue::FixedBytes(bytes) => {
+                visitor.visit_byte_buf(bytes)
+            }
+            TokenValue::String(value) => visitor.visit_string(value),
+            TokenValue::Address(ton_block::MsgAddress::AddrNone) => visitor.visit_none(),
+            TokenValue::Address(address) => visitor.visit_string(address.to_string()),
+            TokenValue::Cell(cell) => match ton_types::serialize_toc(&cell) {
+                Ok(bytes) => visitor.visit_byte_buf(bytes),
+                Err(e) => Err(Error::Custom(e.to_string())),
+            },
+            TokenValue::PublicKey(None) | TokenValue::Optional(_, None) => visitor.visit_none(),
+            TokenValue::PublicKey(Some(key)) => {
+                visitor.visit_some(Deserializer(TokenValue::Bytes(key)))
+            }
+            TokenValue::Optional(_, Some(value)) => visitor.visit_some(Deserializer(*value)),
+            TokenValue::Ref(value) => Deserializer(*value).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            TokenValue::PublicKey(_)
+            | TokenValue::Optional(..)
+            | TokenValue::Address(ton_block::MsgAddress::AddrNone) => self.deserialize_any(visitor),
+            TokenValue::Ref(value) => Deserializer(*value).deserialize_option(visitor),
+            value => visitor.visit_some(Deserializer(value)),
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            TokenValue::Tuple(tokens) => visit_seq(
+                tokens.into_iter().map(|token| token.value).collect(),
+                visitor,
+            ),
+            TokenValue::Bytes(bytes) | TokenValue::FixedBytes(bytes) => {
+                visit_bytes_seq(bytes, visitor)
+            }
+            TokenValue::Ref(value) => Deserializer(*value).deserialize_seq(visitor),
+            value => Deserializer(value).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let expected = "unit variant tag";
+        match self.0 {
+            TokenValue::Uint(int) => match int.number.to_u32() {
+                Some(index) => visitor.visit_enum(U32Deserializer::new(index)),
+                None => Err(Error::UnexpectedValue { expected }),
+            },
+            TokenValue::String(name) => visitor.visit_enum(StrDeserializer::new(&name)),
+            TokenValue::Bytes(name) => match std::str::from_utf8(&name) {
+                Ok(name) => visitor.visit_enum(StrDeserializer::new(name)),
+                Err(_) => Err(Error::UnexpectedValue { expected }),
+            },
+            TokenValue::Ref(value) => {
+                Deserializer(*value).deserialize_enum(name, variants, visitor)
+            }
+            _ => Err(Error::UnexpectedValue { expected }),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct identifier ignored_any
+    }
+}
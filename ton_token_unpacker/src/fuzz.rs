@@ -0,0 +1,275 @@
+//! `arbitrary` support for fuzzing unpackers.
+//!
+//! Token values are generated from a type's derived schema. Most of them are
+//! valid, the rest are mutated: integers one bit wider than declared, values
+//! of a wrong variant, and tuples with missing or renamed components.
+
+use std::marker::PhantomData;
+
+use arbitrary::{Arbitrary, Unstructured};
+use num_bigint::{BigInt, BigUint, Sign};
+use ton_abi::{Param, ParamType, Token, TokenValue};
+use ton_block::{MsgAddrStd, MsgAddress};
+use ton_types::{Cell, SliceData};
+
+use crate::{KnownParamType, KnownParamTypePlain};
+
+const MAX_LEN: usize = 4;
+
+/// Token value shaped after the schema of `T`.
+pub struct FuzzTokenValue<T> {
+    pub value: TokenValue,
+    _marker: PhantomData<T>,
+}
+
+impl<T> FuzzTokenValue<T> {
+    pub fn into_inner(self) -> TokenValue {
+        self.value
+    }
+}
+
+impl<T> std::fmt::Debug for FuzzTokenValue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for FuzzTokenValue<T>
+where
+    T: KnownParamType,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            value: arbitrary_token_value(u, &T::param_type())?,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Tokens shaped after the schema of the plain struct `T`.
+pub struct FuzzTokens<T> {
+    pub tokens: Vec<Token>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> FuzzTokens<T> {
+    pub fn into_inner(self) -> Vec<Token> {
+        self.tokens
+    }
+}
+
+impl<T> std::fmt::Debug for FuzzTokens<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.tokens.fmt(f)
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for FuzzTokens<T>
+where
+    T: KnownParamTypePlain,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tokens: arbitrary_tokens(u, &T::param_type())?,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[derive(Arbitrary, Debug, Copy, Clone, Eq, PartialEq)]
+enum Mutation {
+    None,
+    OffByOneWidth,
+    WrongVariant,
+    MissingField,
+    RenamedField,
+}
+
+impl Mutation {
+    fn pick(u: &mut Unstructured<'_>) -> arbitrary::Result<Self> {
+        // Keep most of the values valid so that fuzzing reaches nested fields
+        if u.ratio(3, 4)? {
+            Ok(Mutation::None)
+        } else {
+            u.arbitrary()
+        }
+    }
+}
+
+/// Generates a possibly malformed token value for the schema.
+pub fn arbitrary_token_value(
+    u: &mut Unstructured<'_>,
+    param_type: &ParamType,
+) -> arbitrary::Result<TokenValue> {
+    let mutation = Mutation::pick(u)?;
+    token_value(u, param_type, mutation, true)
+}
+
+/// Generates a valid token value for the schema.
+pub fn valid_token_value(
+    u: &mut Unstructured<'_>,
+    param_type: &ParamType,
+) -> arbitrary::Result<TokenValue> {
+    token_value(u, param_type, Mutation::None, false)
+}
+
+/// Generates possibly malformed tokens for the params.
+pub fn arbitrary_tokens(
+    u: &mut Unstructured<'_>,
+    params: &[Param],
+) -> arbitrary::Result<Vec<Token>> {
+    let mutation = Mutation::pick(u)?;
+    tokens(u, params, mutation, true)
+}
+
+/// Generates valid tokens for the params.
+pub fn valid_tokens(u: &mut Unstructured<'_>, params: &[Param]) -> arbitrary::Result<Vec<Token>> {
+    tokens(u, params, Mutation::None, false)
+}
+
+/// Generates a value of a nested component, mutated only if the parent may be.
+fn child_value(
+    u: &mut Unstructured<'_>,
+    param_type: &ParamType,
+    mutate: bool,
+) -> arbitrary::Result<TokenValue> {
+    if mutate {
+        arbitrary_token_value(u, param_type)
+    } else {
+        valid_token_value(u, param_type)
+    }
+}
+
+fn tokens(
+    u: &mut Unstructured<'_>,
+    params: &[Param],
+    mutation: Mutation,
+    mutate: bool,
+) -> arbitrary::Result<Vec<Token>> {
+    let mut tokens = params
+        .iter()
+        .map(|param| {
+            Ok(Token::new(
+                &param.name,
+                child_value(u, &param.kind, mutate)?,
+            ))
+        })
+        .collect::<arbitrary::Result<Vec<_>>>()?;
+
+    if !tokens.is_empty() {
+        let index = u.choose_index(tokens.len())?;
+        match mutation {
+            Mutation::MissingField => {
+                tokens.remove(index);
+            }
+            Mutation::RenamedField => tokens[index].name.push('_'),
+            _ => {}
+        }
+    }
+    Ok(tokens)
+}
+
+fn token_value(
+    u: &mut Unstructured<'_>,
+    param_type: &ParamType,
+    mutation: Mutation,
+    mutate: bool,
+) -> arbitrary::Result<TokenValue> {
+    if mutation == Mutation::WrongVariant {
+        let other = wrong_variant(u, param_type)?;
+        return valid_token_value(u, &other);
+    }
+    let widen = (mutation == Mutation::OffByOneWidth) as usize;
+
+    Ok(match param_type {
+        ParamType::Uint(size) => TokenValue::Uint(ton_abi::Uint {
+            number: uint(u, size + widen)?,
+            size: *size,
+        }),
+        ParamType::Int(size) => TokenValue::Int(ton_abi::Int {
+            number: int(u, size + widen)?,
+            size: *size,
+        }),
+        // A degenerate `varuint0` has no room for a value, so only zero fits
+        ParamType::VarUint(size) => {
+            TokenValue::VarUint(*size, uint(u, size.saturating_sub(1) * 8 + widen)?)
+        }
+        ParamType::VarInt(size) => {
+            TokenValue::VarInt(*size, int(u, size.saturating_sub(1) * 8 + widen)?)
+        }
+        ParamType::Bool | ParamType::Unknown => TokenValue::Bool(u.arbitrary()?),
+        ParamType::Tuple(params) => TokenValue::Tuple(tokens(u, params, mutation, mutate)?),
+        ParamType::Array(item) => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            TokenValue::Array(
+                (0..len)
+                    .map(|_| child_value(u, item, mutate))
+                    .collect::<arbitrary::Result<_>>()?,
+            )
+        }
+        ParamType::FixedArray(item, len) => TokenValue::FixedArray(
+            (0..len + widen)
+                .map(|_| child_value(u, item, mutate))
+                .collect::<arbitrary::Result<_>>()?,
+        ),
+        ParamType::Cell => TokenValue::Cell(Cell::default()),
+        ParamType::Map(key, _) => TokenValue::Map(*key.clone(), Default::default()),
+        ParamType::Address => TokenValue::Address(MsgAddress::AddrStd(MsgAddrStd {
+            anycast: None,
+            workchain_id: u.arbitrary()?,
+            address: SliceData::from_raw(u.bytes(32)?.to_vec(), 256),
+        })),
+        ParamType::Bytes => TokenValue::Bytes(u.arbitrary()?),
+        ParamType::FixedBytes(len) => TokenValue::FixedBytes(u.bytes(len + widen)?.to_vec()),
+        ParamType::String => TokenValue::String(u.arbitrary()?),
+        ParamType::Gram => TokenValue::Gram(Default::default()),
+        ParamType::Time => TokenValue::Time(u.arbitrary()?),
+        ParamType::Expire => TokenValue::Expire(u.arbitrary()?),
+        ParamType::PublicKey => TokenValue::PublicKey(match u.arbitrary()? {
+            true => Some(u.bytes(32 + widen)?.to_vec()),
+            false => None,
+        }),
+        ParamType::Optional(inner) => TokenValue::Optional(
+            *inner.clone(),
+            match u.arbitrary()? {
+                true => Some(Box::new(child_value(u, inner, mutate)?)),
+                false => None,
+            },
+        ),
+        ParamType::Ref(inner) => TokenValue::Ref(Box::new(child_value(u, inner, mutate)?)),
+    })
+}
+
+fn wrong_variant(u: &mut Unstructured<'_>, param_type: &ParamType) -> arbitrary::Result<ParamType> {
+    let candidates = [
+        ParamType::Bool,
+        ParamType::Uint(8),
+        ParamType::Int(8),
+        ParamType::Bytes,
+        ParamType::Tuple(Vec::new()),
+    ];
+    let candidates = candidates
+        .iter()
+        .filter(|candidate| *candidate != param_type)
+        .collect::<Vec<_>>();
+    Ok((*u.choose(&candidates)?).clone())
+}
+
+fn uint(u: &mut Unstructured<'_>, bits: usize) -> arbitrary::Result<BigUint> {
+    let bytes = u.bytes(bits.div_ceil(8))?;
+    let mask = (BigUint::from(1u8) << bits) - 1u8;
+    Ok(BigUint::from_bytes_be(bytes) & mask)
+}
+
+fn int(u: &mut Unstructured<'_>, bits: usize) -> arbitrary::Result<BigInt> {
+    if bits == 0 {
+        return Ok(BigInt::from(0));
+    }
+    let magnitude = uint(u, bits - 1)?;
+    let sign = if u.arbitrary()? {
+        Sign::Minus
+    } else {
+        Sign::Plus
+    };
+    Ok(BigInt::from_biguint(sign, magnitude))
+}
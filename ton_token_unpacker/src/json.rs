@@ -0,0 +1,26 @@
+//! Deserialization of token values from the JSON format used by `ton_abi`
+//! (decimal strings for integers, `0:hex` addresses, base64 cells).
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use ton_abi::token::Tokenizer;
+use ton_abi::{Param, ParamType, Token, TokenValue};
+
+pub fn deserialize_token_value<'de, D>(
+    param_type: &ParamType,
+    deserializer: D,
+) -> Result<TokenValue, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Tokenizer::tokenize_parameter(param_type, &value).map_err(D::Error::custom)
+}
+
+pub fn deserialize_tokens<'de, D>(params: &[Param], deserializer: D) -> Result<Vec<Token>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Tokenizer::tokenize_all_params(params, &value).map_err(D::Error::custom)
+}
@@ -1,10 +1,40 @@
+#[cfg(feature = "bitflags")]
+pub use bitflags;
 pub use num_traits;
+pub use serde;
+#[doc(hidden)]
+pub use ton_abi;
+
+pub mod abi_type;
+pub mod cell;
+pub mod de;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod payload;
+#[cfg(feature = "roundtrip")]
+pub mod roundtrip;
+pub mod with;
+
+pub use abi_type::AbiCompatible;
+pub use de::from_token_value;
+pub use payload::CellPayload;
+pub use ton_token_core::StandaloneToken;
+pub use ton_token_packer::{FriendlyAddress, PublicKey, Signature, TokenAmount};
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use num_traits::ToPrimitive;
-use ton_abi::{Token, TokenValue};
-use ton_block::{MsgAddrStd, MsgAddressInt};
+use ton_abi::{Param, ParamType, Token, TokenValue};
+use ton_block::{MsgAddrExt, MsgAddrStd, MsgAddress, MsgAddressInt};
 use ton_types::{Cell, UInt256};
 
+use abi_type::{FromAddress, FromInner};
+
 pub trait IgnoreOutput: Sized {
     fn ignore_output(self) -> Result<(), UnpackerError> {
         Ok(())
@@ -23,62 +53,212 @@ impl IntoUnpacker for Vec<Token> {
     type Iter = std::vec::IntoIter<Token>;
 
     fn into_unpacker(self) -> ContractOutputUnpacker<Self::Iter> {
-        ContractOutputUnpacker(self.into_iter())
+        ContractOutputUnpacker::new(self.into_iter())
     }
 }
 
 #[derive(Debug)]
-pub struct ContractOutputUnpacker<I>(I);
+pub struct ContractOutputUnpacker<I: Iterator<Item = Token>> {
+    tokens: std::iter::Peekable<I>,
+    position: usize,
+}
 
 impl<I: Iterator<Item = Token>> ContractOutputUnpacker<I> {
+    pub fn new(tokens: I) -> Self {
+        Self {
+            tokens: tokens.peekable(),
+            position: 0,
+        }
+    }
+
+    /// Index of the next token.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
     pub fn unpack_next<T>(&mut self) -> ContractResult<T>
     where
         TokenValue: UnpackToken<T>,
     {
-        self.0.next().unpack()
+        let position = self.position;
+        let token = self
+            .tokens
+            .next()
+            .ok_or(UnpackerError::UnexpectedEnd { position })?;
+        self.position += 1;
+
+        let name = token.name;
+        token
+            .value
+            .unpack()
+            .map_err(|e| UnpackerError::InvalidToken {
+                position,
+                name,
+                source: Box::new(e),
+            })
+    }
+
+    /// Skips tokens until the one with the specified name and unpacks it.
+    pub fn unpack_named<T>(&mut self, name: &str) -> ContractResult<T>
+    where
+        TokenValue: UnpackToken<T>,
+    {
+        loop {
+            match self.peek_name() {
+                Some(next) if next == name => return self.unpack_next(),
+                Some(_) => self.skip(1)?,
+                None => {
+                    return Err(UnpackerError::TokenNotFound {
+                        name: name.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Unpacks all remaining tokens into a plain struct.
+    pub fn unpack_rest<T>(&mut self) -> ContractResult<T>
+    where
+        T: UnpackTokens,
+    {
+        let start = self.position;
+        let mut last_name = String::new();
+        let result = {
+            let position = &mut self.position;
+            let mut tokens = self.tokens.by_ref().inspect(|token| {
+                *position += 1;
+                last_name.clone_from(&token.name);
+            });
+            T::unpack_tokens(&mut tokens)
+        };
+
+        result.map_err(|e| UnpackerError::InvalidToken {
+            position: std::cmp::max(self.position, start + 1) - 1,
+            name: last_name,
+            source: Box::new(e),
+        })
+    }
+
+    pub fn peek_name(&mut self) -> Option<&str> {
+        self.tokens.peek().map(|token| token.name.as_str())
+    }
+
+    pub fn skip(&mut self, n: usize) -> ContractResult<()> {
+        for _ in 0..n {
+            if self.tokens.next().is_none() {
+                return Err(UnpackerError::UnexpectedEnd {
+                    position: self.position,
+                });
+            }
+            self.position += 1;
+        }
+        Ok(())
+    }
+
+    pub fn expect_end(&mut self) -> ContractResult<()> {
+        match self.tokens.peek() {
+            Some(token) => Err(UnpackerError::UnexpectedToken {
+                position: self.position,
+                name: token.name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<I: ExactSizeIterator<Item = Token>> ContractOutputUnpacker<I> {
+    /// Number of tokens left.
+    pub fn remaining(&self) -> usize {
+        self.tokens.len()
     }
 }
 
 pub trait UnpackToken<T> {
     fn unpack(self) -> ContractResult<T>;
+
+    /// Unpacks an array of values, `u8` overrides it to unpack `bytes`.
+    #[doc(hidden)]
+    fn unpack_vec(self) -> ContractResult<Vec<T>>
+    where
+        Self: Sized + Into<TokenValue>,
+        TokenValue: UnpackToken<T>,
+    {
+        match self.into() {
+            TokenValue::Array(tokens) | TokenValue::FixedArray(tokens) => tokens,
+            _ => return Err(UnpackerError::InvalidAbi),
+        }
+        .into_iter()
+        .map(UnpackToken::unpack)
+        .collect()
+    }
+}
+
+pub trait UnpackTokens: Sized {
+    fn unpack_tokens<I>(tokens: &mut I) -> ContractResult<Self>
+    where
+        I: Iterator<Item = Token>;
+
+    /// Positional counterpart of [`UnpackTokens::unpack_tokens`] which doesn't check names.
+    fn unpack_token_values<I>(values: &mut I) -> ContractResult<Self>
+    where
+        I: Iterator<Item = TokenValue>;
+}
+
+/// Positional, name-less decoding of values returned by `ton_abi` decoders.
+pub trait UnpackTokenValues<T> {
+    fn unpack_values(self) -> ContractResult<T>;
 }
 
 impl UnpackToken<MsgAddrStd> for TokenValue {
     fn unpack(self) -> ContractResult<MsgAddrStd> {
-        match self {
-            TokenValue::Address(ton_block::MsgAddress::AddrStd(address)) => Ok(address),
-            _ => Err(UnpackerError::InvalidAbi),
-        }
+        unpack_address(self)
     }
 }
 
 impl UnpackToken<MsgAddressInt> for TokenValue {
     fn unpack(self) -> ContractResult<MsgAddressInt> {
-        match self {
-            TokenValue::Address(ton_block::MsgAddress::AddrStd(addr)) => {
-                Ok(MsgAddressInt::AddrStd(addr))
-            }
-            TokenValue::Address(ton_block::MsgAddress::AddrVar(addr)) => {
-                Ok(MsgAddressInt::AddrVar(addr))
-            }
-            _ => Err(UnpackerError::InvalidAbi),
-        }
+        unpack_address(self)
     }
 }
 
-impl UnpackToken<Cell> for TokenValue {
-    fn unpack(self) -> ContractResult<Cell> {
-        match self {
-            TokenValue::Cell(cell) => Ok(cell),
-            _ => Err(UnpackerError::InvalidAbi),
-        }
+impl UnpackToken<MsgAddrExt> for TokenValue {
+    fn unpack(self) -> ContractResult<MsgAddrExt> {
+        unpack_address(self)
+    }
+}
+
+impl UnpackToken<MsgAddress> for TokenValue {
+    fn unpack(self) -> ContractResult<MsgAddress> {
+        unpack_address(self)
+    }
+}
+
+impl UnpackToken<FriendlyAddress> for TokenValue {
+    fn unpack(self) -> ContractResult<FriendlyAddress> {
+        unpack_address(self)
+    }
+}
+
+impl UnpackToken<Option<MsgAddressInt>> for TokenValue {
+    fn unpack(self) -> ContractResult<Option<MsgAddressInt>> {
+        unpack_address(self)
+    }
+}
+
+fn unpack_address<T>(value: TokenValue) -> ContractResult<T>
+where
+    T: FromAddress,
+{
+    match value {
+        TokenValue::Address(address) => T::from_address(address),
+        _ => Err(UnpackerError::InvalidAbi),
     }
 }
 
-impl UnpackToken<Vec<u8>> for TokenValue {
-    fn unpack(self) -> ContractResult<Vec<u8>> {
+impl UnpackToken<Cell> for TokenValue {
+    fn unpack(self) -> ContractResult<Cell> {
         match self {
-            TokenValue::Bytes(bytes) => Ok(bytes),
+            TokenValue::Cell(cell) => Ok(cell),
             _ => Err(UnpackerError::InvalidAbi),
         }
     }
@@ -111,6 +291,54 @@ impl UnpackToken<UInt256> for TokenValue {
     }
 }
 
+impl UnpackToken<PublicKey> for TokenValue {
+    fn unpack(self) -> ContractResult<PublicKey> {
+        match self {
+            TokenValue::Uint(data) if data.number.bits() <= 256 => {
+                let bytes = data.number.to_bytes_be();
+                let mut result = [0; 32];
+                result[32 - bytes.len()..].copy_from_slice(&bytes);
+                Ok(PublicKey::from_bytes(result))
+            }
+            TokenValue::PublicKey(Some(bytes)) => {
+                PublicKey::from_slice(&bytes).map_err(|_| UnpackerError::InvalidLength {
+                    expected: ton_token_packer::crypto::PUBLIC_KEY_LEN,
+                    found: bytes.len(),
+                })
+            }
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+impl UnpackToken<Signature> for TokenValue {
+    fn unpack(self) -> ContractResult<Signature> {
+        match self {
+            TokenValue::Bytes(bytes) => {
+                Signature::from_slice(&bytes).map_err(|_| UnpackerError::InvalidLength {
+                    expected: ton_token_packer::crypto::SIGNATURE_LEN,
+                    found: bytes.len(),
+                })
+            }
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+impl<const DECIMALS: u8> UnpackToken<TokenAmount<DECIMALS>> for TokenValue {
+    fn unpack(self) -> ContractResult<TokenAmount<DECIMALS>> {
+        match self {
+            TokenValue::Uint(data) => Ok(TokenAmount::from_units(
+                data.number.to_u128().ok_or(UnpackerError::InvalidAbi)?,
+            )),
+            TokenValue::VarUint(_, number) => Ok(TokenAmount::from_units(
+                number.to_u128().ok_or(UnpackerError::InvalidAbi)?,
+            )),
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
 impl UnpackToken<i8> for TokenValue {
     fn unpack(self) -> ContractResult<i8> {
         match self {
@@ -127,6 +355,13 @@ impl UnpackToken<u8> for TokenValue {
             _ => Err(UnpackerError::InvalidAbi),
         }
     }
+
+    fn unpack_vec(self) -> ContractResult<Vec<u8>> {
+        match self {
+            TokenValue::Bytes(bytes) => Ok(bytes),
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
 }
 
 impl UnpackToken<u16> for TokenValue {
@@ -205,19 +440,49 @@ where
     }
 }
 
+impl<T> UnpackToken<Box<T>> for TokenValue
+where
+    TokenValue: UnpackToken<T>,
+{
+    fn unpack(self) -> ContractResult<Box<T>> {
+        self.unpack().map(FromInner::from_inner)
+    }
+}
+
+impl<T> UnpackToken<Rc<T>> for TokenValue
+where
+    TokenValue: UnpackToken<T>,
+{
+    fn unpack(self) -> ContractResult<Rc<T>> {
+        self.unpack().map(FromInner::from_inner)
+    }
+}
+
+impl<T> UnpackToken<Arc<T>> for TokenValue
+where
+    TokenValue: UnpackToken<T>,
+{
+    fn unpack(self) -> ContractResult<Arc<T>> {
+        self.unpack().map(FromInner::from_inner)
+    }
+}
+
+impl<'a, B> UnpackToken<Cow<'a, B>> for TokenValue
+where
+    B: ToOwned + ?Sized,
+    TokenValue: UnpackToken<B::Owned>,
+{
+    fn unpack(self) -> ContractResult<Cow<'a, B>> {
+        self.unpack().map(FromInner::from_inner)
+    }
+}
+
 impl<T> UnpackToken<Vec<T>> for TokenValue
 where
-    T: StandaloneToken,
     TokenValue: UnpackToken<T>,
 {
     fn unpack(self) -> ContractResult<Vec<T>> {
-        match self {
-            TokenValue::Array(tokens) | TokenValue::FixedArray(tokens) => tokens,
-            _ => return Err(UnpackerError::InvalidAbi),
-        }
-        .into_iter()
-        .map(UnpackToken::unpack)
-        .collect()
+        <TokenValue as UnpackToken<T>>::unpack_vec(self)
     }
 }
 
@@ -230,17 +495,220 @@ where
     }
 }
 
-pub trait StandaloneToken {}
-impl StandaloneToken for MsgAddressInt {}
-impl StandaloneToken for MsgAddrStd {}
-impl StandaloneToken for UInt256 {}
-impl StandaloneToken for u16 {}
-impl StandaloneToken for u32 {}
-impl StandaloneToken for u64 {}
-impl StandaloneToken for u128 {}
-impl StandaloneToken for bool {}
-impl StandaloneToken for Vec<u8> {}
-impl StandaloneToken for TokenValue {}
+impl<T> UnpackTokenValues<Vec<T>> for Vec<TokenValue>
+where
+    TokenValue: UnpackToken<T>,
+{
+    fn unpack_values(self) -> ContractResult<Vec<T>> {
+        self.into_iter().map(UnpackToken::unpack).collect()
+    }
+}
+
+impl<T, const N: usize> UnpackTokenValues<[T; N]> for Vec<TokenValue>
+where
+    TokenValue: UnpackToken<T>,
+{
+    fn unpack_values(self) -> ContractResult<[T; N]> {
+        let values: Vec<T> = self.unpack_values()?;
+        <[T; N]>::try_from(values).map_err(|_| UnpackerError::InvalidAbi)
+    }
+}
+
+macro_rules! impl_unpack_token_values_for_tuple {
+    ($($ty:ident),+) => {
+        impl<$($ty),+> UnpackTokenValues<($($ty,)+)> for Vec<TokenValue>
+        where
+            $(TokenValue: UnpackToken<$ty>),+
+        {
+            fn unpack_values(self) -> ContractResult<($($ty,)+)> {
+                let mut values = self.into_iter();
+                let result = ($(UnpackToken::<$ty>::unpack(values.next())?,)+);
+                match values.next() {
+                    Some(_) => Err(UnpackerError::InvalidAbi),
+                    None => Ok(result),
+                }
+            }
+        }
+    };
+}
+
+impl_unpack_token_values_for_tuple!(A);
+impl_unpack_token_values_for_tuple!(A, B);
+impl_unpack_token_values_for_tuple!(A, B, C);
+impl_unpack_token_values_for_tuple!(A, B, C, D);
+impl_unpack_token_values_for_tuple!(A, B, C, D, E);
+impl_unpack_token_values_for_tuple!(A, B, C, D, E, F);
+impl_unpack_token_values_for_tuple!(A, B, C, D, E, F, G);
+impl_unpack_token_values_for_tuple!(A, B, C, D, E, F, G, H);
+
+pub trait KnownParamType {
+    fn param_type() -> ParamType;
+
+    /// Param type of an array of values, `u8` overrides it with `bytes`.
+    #[doc(hidden)]
+    fn vec_param_type() -> ParamType {
+        ParamType::Array(Box::new(Self::param_type()))
+    }
+}
+
+pub trait KnownParamTypePlain {
+    fn param_type() -> Vec<Param>;
+}
+
+impl KnownParamType for MsgAddrStd {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+}
+
+impl KnownParamType for MsgAddressInt {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+}
+
+impl KnownParamType for MsgAddrExt {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+}
+
+impl KnownParamType for MsgAddress {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+}
+
+impl KnownParamType for FriendlyAddress {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+}
+
+impl KnownParamType for Option<MsgAddressInt> {
+    fn param_type() -> ParamType {
+        ParamType::Address
+    }
+}
+
+impl KnownParamType for Cell {
+    fn param_type() -> ParamType {
+        ParamType::Cell
+    }
+}
+
+impl KnownParamType for String {
+    fn param_type() -> ParamType {
+        ParamType::Bytes
+    }
+}
+
+impl KnownParamType for UInt256 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(256)
+    }
+}
+
+impl KnownParamType for PublicKey {
+    fn param_type() -> ParamType {
+        ParamType::Uint(256)
+    }
+}
+
+impl KnownParamType for Signature {
+    fn param_type() -> ParamType {
+        ParamType::Bytes
+    }
+}
+
+impl<const DECIMALS: u8> KnownParamType for TokenAmount<DECIMALS> {
+    fn param_type() -> ParamType {
+        ParamType::Uint(128)
+    }
+}
+
+impl KnownParamType for i8 {
+    fn param_type() -> ParamType {
+        ParamType::Int(8)
+    }
+}
+
+impl KnownParamType for u8 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(8)
+    }
+
+    fn vec_param_type() -> ParamType {
+        ParamType::Bytes
+    }
+}
+
+impl KnownParamType for u16 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(16)
+    }
+}
+
+impl KnownParamType for u32 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(32)
+    }
+}
+
+impl KnownParamType for u64 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(64)
+    }
+}
+
+impl KnownParamType for u128 {
+    fn param_type() -> ParamType {
+        ParamType::Uint(128)
+    }
+}
+
+impl KnownParamType for bool {
+    fn param_type() -> ParamType {
+        ParamType::Bool
+    }
+}
+
+impl<T: KnownParamType> KnownParamType for Box<T> {
+    fn param_type() -> ParamType {
+        T::param_type()
+    }
+}
+
+impl<T: KnownParamType> KnownParamType for Rc<T> {
+    fn param_type() -> ParamType {
+        T::param_type()
+    }
+}
+
+impl<T: KnownParamType> KnownParamType for Arc<T> {
+    fn param_type() -> ParamType {
+        T::param_type()
+    }
+}
+
+impl<B> KnownParamType for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: KnownParamType,
+{
+    fn param_type() -> ParamType {
+        B::Owned::param_type()
+    }
+}
+
+impl<T> KnownParamType for Vec<T>
+where
+    T: KnownParamType,
+{
+    fn param_type() -> ParamType {
+        T::vec_param_type()
+    }
+}
 
 pub type ContractResult<T> = Result<T, UnpackerError>;
 
@@ -250,4 +718,42 @@ pub enum UnpackerError {
     InvalidAbi,
     #[error("Invalid name (expected {expected:?}, found {found:?})")]
     InvalidName { expected: String, found: String },
+    #[error("Invalid token #{position} {name:?}: {source}")]
+    InvalidToken {
+        position: usize,
+        name: String,
+        source: Box<UnpackerError>,
+    },
+    #[error("Unexpected token #{position} {name:?}")]
+    UnexpectedToken { position: usize, name: String },
+    #[error("Unexpected end of output at token #{position}")]
+    UnexpectedEnd { position: usize },
+    #[error("Token {name:?} not found")]
+    TokenNotFound { name: String },
+    #[error("Invalid cell: {0}")]
+    InvalidCell(String),
+    #[error("Invalid length (expected {expected} bytes, found {found})")]
+    InvalidLength { expected: usize, found: usize },
+}
+
+/// Implements [`UnpackToken`] for a `bitflags` type.
+///
+/// Unknown bits are retained by default, use `impl_bitflags!(Type, strict)`
+/// to reject them instead.
+#[cfg(feature = "bitflags")]
+#[macro_export]
+macro_rules! impl_bitflags {
+    (@impl $ty:ty, $with:ident) => {
+        impl $crate::UnpackToken<$ty> for $crate::ton_abi::TokenValue {
+            fn unpack(self) -> $crate::ContractResult<$ty> {
+                $crate::with::$with::unpack(&self)
+            }
+        }
+    };
+    ($ty:ty) => {
+        $crate::impl_bitflags!(@impl $ty, bitflags);
+    };
+    ($ty:ty, strict) => {
+        $crate::impl_bitflags!(@impl $ty, bitflags_strict);
+    };
 }
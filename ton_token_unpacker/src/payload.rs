@@ -0,0 +1,128 @@
+//! Cells with contents of a known layout, e.g. a `TvmCell payload` of a transfer notification.
+
+use std::marker::PhantomData;
+
+use ton_abi::{ParamType, TokenValue};
+use ton_token_packer::{BuildTokenValue, PackerError, TryBuildTokenValue};
+use ton_types::Cell;
+
+use crate::{ContractResult, KnownParamType, StandaloneToken, UnpackToken, UnpackerError};
+
+/// Cell which encodes `T`.
+///
+/// Packed and unpacked as a plain `cell`, the contents are only decoded on [`CellPayload::decode`].
+pub struct CellPayload<T> {
+    cell: Cell,
+    _marker: PhantomData<T>,
+}
+
+impl<T> CellPayload<T> {
+    pub fn from_cell(cell: Cell) -> Self {
+        Self {
+            cell,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn cell(&self) -> &Cell {
+        &self.cell
+    }
+
+    pub fn into_cell(self) -> Cell {
+        self.cell
+    }
+}
+
+impl<T> CellPayload<T>
+where
+    T: TryBuildTokenValue,
+{
+    /// Encodes the value with the default [`ABI_VERSION`](crate::cell::ABI_VERSION).
+    pub fn new(value: T) -> Result<Self, PackerError> {
+        ton_token_packer::cell::build_cell(value, crate::cell::ABI_VERSION).map(Self::from_cell)
+    }
+}
+
+impl<T> CellPayload<T>
+where
+    T: KnownParamType,
+    TokenValue: UnpackToken<T>,
+{
+    pub fn decode(&self) -> ContractResult<T> {
+        crate::cell::unpack_cell(self.cell.clone(), crate::cell::ABI_VERSION)
+    }
+}
+
+impl<T> Clone for CellPayload<T> {
+    fn clone(&self) -> Self {
+        Self::from_cell(self.cell.clone())
+    }
+}
+
+impl<T> std::fmt::Debug for CellPayload<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CellPayload").field(&self.cell).finish()
+    }
+}
+
+impl<T> PartialEq for CellPayload<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cell == other.cell
+    }
+}
+
+impl<T> Eq for CellPayload<T> {}
+
+impl<T> From<Cell> for CellPayload<T> {
+    fn from(cell: Cell) -> Self {
+        Self::from_cell(cell)
+    }
+}
+
+impl<T> From<CellPayload<T>> for Cell {
+    fn from(payload: CellPayload<T>) -> Self {
+        payload.cell
+    }
+}
+
+impl<T> BuildTokenValue for CellPayload<T> {
+    fn token_value(self) -> TokenValue {
+        TokenValue::Cell(self.cell)
+    }
+}
+
+impl<T> UnpackToken<CellPayload<T>> for TokenValue {
+    fn unpack(self) -> ContractResult<CellPayload<T>> {
+        match self {
+            TokenValue::Cell(cell) => Ok(CellPayload::from_cell(cell)),
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+impl<T> KnownParamType for CellPayload<T> {
+    fn param_type() -> ParamType {
+        ParamType::Cell
+    }
+}
+
+impl<T> StandaloneToken for CellPayload<T> {}
+
+impl<T> crate::AbiCompatible<crate::abi_type::Cell> for CellPayload<T> {}
+
+#[diagnostic::do_not_recommend]
+impl<T> ton_token_packer::abi_type::IntoAbi<crate::abi_type::Cell> for CellPayload<T> {
+    fn into_abi(self) -> TokenValue {
+        TokenValue::Cell(self.into())
+    }
+}
+
+#[diagnostic::do_not_recommend]
+impl<T> crate::abi_type::FromAbi<crate::abi_type::Cell> for CellPayload<T> {
+    fn from_abi(value: TokenValue) -> ContractResult<Self> {
+        match value {
+            TokenValue::Cell(cell) => Ok(cell.into()),
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
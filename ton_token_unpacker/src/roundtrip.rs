@@ -0,0 +1,124 @@
+//! Round-trip property testing for derived types.
+//!
+//! Values are generated with `proptest`, packed, encoded into a cell with
+//! `ton_abi`, decoded back and unpacked. A failing case is minimized by
+//! `proptest` and reported in the panic message.
+
+use std::fmt::Debug;
+
+use proptest::prelude::*;
+use proptest::test_runner::{TestCaseError, TestRunner};
+use ton_abi::{Param, Token, TokenValue};
+use ton_token_packer::{BuildTokenValue, PackTokens};
+use ton_types::SliceData;
+
+use crate::cell::ABI_VERSION;
+use crate::{KnownParamType, KnownParamTypePlain, UnpackToken, UnpackTokens};
+
+/// Checks that random instances of `T` survive packing, cell encoding and unpacking.
+pub fn assert_roundtrip<T>()
+where
+    T: Arbitrary + BuildTokenValue + KnownParamType + Clone + Debug + PartialEq,
+    TokenValue: UnpackToken<T>,
+{
+    assert_roundtrip_with(any::<T>())
+}
+
+pub fn assert_roundtrip_with<S>(strategy: S)
+where
+    S: Strategy,
+    S::Value: BuildTokenValue + KnownParamType + Clone + Debug + PartialEq,
+    TokenValue: UnpackToken<S::Value>,
+{
+    run(strategy, roundtrip)
+}
+
+/// Same as [`assert_roundtrip`] for plain structs.
+pub fn assert_roundtrip_plain<T>()
+where
+    T: Arbitrary + PackTokens + UnpackTokens + KnownParamTypePlain + Clone + Debug + PartialEq,
+{
+    assert_roundtrip_plain_with(any::<T>())
+}
+
+pub fn assert_roundtrip_plain_with<S>(strategy: S)
+where
+    S: Strategy,
+    S::Value: PackTokens + UnpackTokens + KnownParamTypePlain + Clone + Debug + PartialEq,
+{
+    run(strategy, roundtrip_plain)
+}
+
+/// Packs the value into a cell and unpacks it back.
+pub fn roundtrip<T>(value: T) -> Result<T, String>
+where
+    T: BuildTokenValue + KnownParamType,
+    TokenValue: UnpackToken<T>,
+{
+    let params = [Param::new("value", T::param_type())];
+    let tokens = [Token::new("value", value.token_value())];
+    encode_decode(&params, &tokens)?
+        .into_iter()
+        .next()
+        .unpack()
+        .map_err(|e| e.to_string())
+}
+
+/// Packs the plain struct into a cell and unpacks it back.
+pub fn roundtrip_plain<T>(value: T) -> Result<T, String>
+where
+    T: PackTokens + UnpackTokens + KnownParamTypePlain,
+{
+    let params = T::param_type();
+    let tokens = value.pack();
+    let mut tokens = encode_decode(&params, &tokens)?.into_iter();
+    T::unpack_tokens(&mut tokens).map_err(|e| e.to_string())
+}
+
+fn encode_decode(params: &[Param], tokens: &[Token]) -> Result<Vec<Token>, String> {
+    let cell = TokenValue::pack_values_into_chain(tokens, Vec::new(), ABI_VERSION)
+        .and_then(|builder| builder.into_cell())
+        .map_err(|e| format!("failed to encode: {}", e))?;
+    TokenValue::decode_params(params, SliceData::from(cell), ABI_VERSION)
+        .map_err(|e| format!("failed to decode: {}", e))
+}
+
+fn run<S, F>(strategy: S, roundtrip: F)
+where
+    S: Strategy,
+    S::Value: Clone + Debug + PartialEq,
+    F: Fn(S::Value) -> Result<S::Value, String>,
+{
+    let mut runner = TestRunner::default();
+    let result = runner.run(&strategy, |value| {
+        let unpacked = roundtrip(value.clone()).map_err(TestCaseError::fail)?;
+        prop_assert_eq!(unpacked, value);
+        Ok(())
+    });
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+}
+
+/// Strategies for types which don't implement `Arbitrary`,
+/// for use with `#[proptest(strategy = "...")]`.
+pub mod strategy {
+    use proptest::prelude::*;
+    use ton_block::MsgAddressInt;
+    use ton_types::{SliceData, UInt256};
+
+    pub fn address() -> impl Strategy<Value = MsgAddressInt> {
+        (prop_oneof![Just(0), Just(-1)], any::<[u8; 32]>()).prop_map(|(workchain_id, address)| {
+            MsgAddressInt::with_standart(
+                None,
+                workchain_id,
+                SliceData::from_raw(address.to_vec(), 256),
+            )
+            .unwrap()
+        })
+    }
+
+    pub fn uint256() -> impl Strategy<Value = UInt256> {
+        any::<[u8; 32]>().prop_map(UInt256::from)
+    }
+}
@@ -0,0 +1,114 @@
+//! Unpacking halves of ready-made `#[abi(with = "...")]` adapters.
+//!
+//! The matching `pack` functions live under the same paths in
+//! `ton_token_packer::with`, `ton_token::with` combines both halves.
+
+/// `String` with hex encoded data, unpacked from `bytes`.
+pub mod hex {
+    use ton_abi::TokenValue;
+
+    use crate::{ContractResult, UnpackerError};
+
+    pub fn unpack(value: &TokenValue) -> ContractResult<String> {
+        match value {
+            TokenValue::Bytes(bytes) => Ok(::hex::encode(bytes)),
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+/// `SystemTime`, unpacked from `uint32` unix timestamp in seconds.
+pub mod unix_time {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use num_traits::ToPrimitive;
+    use ton_abi::TokenValue;
+
+    use crate::{ContractResult, UnpackerError};
+
+    pub fn unpack(value: &TokenValue) -> ContractResult<SystemTime> {
+        match value {
+            TokenValue::Uint(data) => {
+                let seconds = data.number.to_u32().ok_or(UnpackerError::InvalidAbi)?;
+                Ok(UNIX_EPOCH + Duration::from_secs(seconds as u64))
+            }
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+/// `TokenAmount<9>`, unpacked from `uint128` nanotons.
+pub mod nanotons {
+    use num_traits::ToPrimitive;
+    use ton_abi::TokenValue;
+    use ton_token_packer::amount::TokenAmount;
+
+    use crate::{ContractResult, UnpackerError};
+
+    pub fn unpack(value: &TokenValue) -> ContractResult<TokenAmount<9>> {
+        match value {
+            TokenValue::Uint(data) => {
+                let nanotons = data.number.to_u128().ok_or(UnpackerError::InvalidAbi)?;
+                Ok(TokenAmount::from_units(nanotons))
+            }
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+/// Any amount convertible from `u128`, unpacked from `varuint16`.
+pub mod varuint16 {
+    use num_traits::ToPrimitive;
+    use ton_abi::TokenValue;
+
+    use crate::{ContractResult, UnpackerError};
+
+    pub fn unpack<T>(value: &TokenValue) -> ContractResult<T>
+    where
+        T: From<u128>,
+    {
+        match value {
+            TokenValue::VarUint(16, number) => number
+                .to_u128()
+                .map(T::from)
+                .ok_or(UnpackerError::InvalidAbi),
+            _ => Err(UnpackerError::InvalidAbi),
+        }
+    }
+}
+
+/// Any `bitflags` type, unpacked from unsigned integer. Unknown bits are retained.
+#[cfg(feature = "bitflags")]
+pub mod bitflags {
+    use bitflags::Flags;
+    use ton_abi::TokenValue;
+
+    use crate::{ContractResult, UnpackToken};
+
+    pub fn unpack<T>(value: &TokenValue) -> ContractResult<T>
+    where
+        T: Flags,
+        TokenValue: UnpackToken<T::Bits>,
+    {
+        let bits = value.clone().unpack()?;
+        Ok(T::from_bits_retain(bits))
+    }
+}
+
+/// Any `bitflags` type, unpacked from unsigned integer. Unknown bits are rejected.
+#[cfg(feature = "bitflags")]
+pub mod bitflags_strict {
+    use bitflags::Flags;
+    use ton_abi::TokenValue;
+
+    use crate::{ContractResult, UnpackToken, UnpackerError};
+
+    pub fn unpack<T>(value: &TokenValue) -> ContractResult<T>
+    where
+        T: Flags,
+        TokenValue: UnpackToken<T::Bits>,
+    {
+        let bits = value.clone().unpack()?;
+        T::from_bits(bits).ok_or(UnpackerError::InvalidAbi)
+    }
+}